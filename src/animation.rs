@@ -0,0 +1,159 @@
+use vector3::Vector3;
+
+// How a value changes from one key to the next.
+#[derive(Clone, Copy)]
+pub enum Interpolation
+{
+    // Holds the value until the next key.
+    Step,
+    Linear,
+    // Eases between the keys along a cubic Bezier timing curve from (0, 0) to (1, 1), given its two control points
+    // as x1, y1, x2 and y2, like CSS timing functions. The x coordinates should be within zero and one.
+    Bezier(f64, f64, f64, f64)
+}
+
+pub const EASE_IN_OUT: Interpolation = Interpolation::Bezier(0.42, 0.0, 0.58, 1.0);
+
+impl Interpolation
+{
+    // Maps how far along between two keys the time is, to how far between their values the value is.
+    pub fn ease(&self, f: f64) -> f64
+    {
+        match *self
+        {
+            Interpolation::Step => 0.0,
+            Interpolation::Linear => f,
+            Interpolation::Bezier(x1, y1, x2, y2) =>
+            {
+                let bezier = |s: f64, a: f64, b: f64| 3.0 * (1.0 - s) * (1.0 - s) * s * a + 3.0 * (1.0 - s) * s * s * b + s * s * s;
+                // The curve's x only ever increases, so the point on it at time f can be found by bisection.
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..48
+                {
+                    let s = (low + high) / 2.0;
+                    if bezier(s, x1, x2) < f { low = s } else { high = s }
+                }
+                bezier((low + high) / 2.0, y1, y2)
+            }
+        }
+    }
+}
+
+pub trait Animatable: Copy
+{
+    fn lerp(&self, other: &Self, f: f64) -> Self;
+}
+
+impl Animatable for f64
+{
+    fn lerp(&self, other: &f64, f: f64) -> f64
+    {
+        self + (other - self) * f
+    }
+}
+
+impl Animatable for Vector3
+{
+    fn lerp(&self, other: &Vector3, f: f64) -> Vector3
+    {
+        *self + (*other - *self) * f
+    }
+}
+
+// A value at a time, and how it changes on the way to the next key.
+#[derive(Clone, Copy)]
+pub struct Key<T>
+{
+    pub time: f64,
+    pub value: T,
+    pub interpolation: Interpolation
+}
+
+impl<T> Key<T>
+{
+    pub fn new(time: f64, value: T, interpolation: Interpolation) -> Key<T>
+    {
+        Key { time: time, value: value, interpolation: interpolation }
+    }
+}
+
+// A value changing over time through a list of keys. Before the first key and after the last, it stays at their value.
+#[derive(Clone)]
+pub struct Track<T>
+{
+    pub keys: Vec<Key<T>>
+}
+
+impl<T: Animatable> Track<T>
+{
+    pub fn new(keys: Vec<Key<T>>) -> Track<T>
+    {
+        assert!(!keys.is_empty(), "Tracks need at least one key");
+        let mut keys = keys;
+        keys.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+        Track { keys: keys }
+    }
+
+    pub fn constant(value: T) -> Track<T>
+    {
+        Track::new(vec![Key::new(0.0, value, Interpolation::Step)])
+    }
+
+    pub fn linear(keys: Vec<(f64, T)>) -> Track<T>
+    {
+        Track::new(keys.into_iter().map(|(time, value)| Key::new(time, value, Interpolation::Linear)).collect())
+    }
+
+    pub fn value_at(&self, time: f64) -> T
+    {
+        let next = self.keys.iter().position(|k| k.time > time).unwrap_or(self.keys.len());
+        if next == 0
+        {
+            return self.keys[0].value;
+        }
+        if next == self.keys.len()
+        {
+            return self.keys[next - 1].value;
+        }
+
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        a.value.lerp(&b.value, a.interpolation.ease((time - a.time) / (b.time - a.time)))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use animation::{Interpolation, Key, Track, EASE_IN_OUT};
+
+    #[test]
+    fn tracks_interpolate_between_keys()
+    {
+        let track = Track::new(vec![
+            Key::new(2.0, 10.0, Interpolation::Step),
+            Key::new(0.0, 0.0, Interpolation::Linear),
+            Key::new(1.0, 4.0, EASE_IN_OUT),
+            Key::new(3.0, 20.0, Interpolation::Linear)
+        ]);
+        assert_eq!(track.value_at(-1.0), 0.0);
+        assert_eq!(track.value_at(0.25), 1.0);
+        // The ease in and out is symmetric, slow at the ends and fast in the middle.
+        assert!((track.value_at(1.5) - 7.0).abs() < 1e-9);
+        assert!(track.value_at(1.1) < 4.6 && track.value_at(1.9) > 9.4);
+        assert_eq!(track.value_at(2.5), 10.0);
+        assert_eq!(track.value_at(3.0), 20.0);
+        assert_eq!(track.value_at(5.0), 20.0);
+    }
+
+    #[test]
+    fn bezier_timing_curves_match_their_ends()
+    {
+        for &curve in [EASE_IN_OUT, Interpolation::Bezier(0.1, 0.8, 0.3, 1.2)].iter()
+        {
+            assert!(curve.ease(0.0).abs() < 1e-9 && (curve.ease(1.0) - 1.0).abs() < 1e-9);
+        }
+        // With control points on the diagonal, the curve is a straight line.
+        let straight = Interpolation::Bezier(1.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0);
+        assert!((straight.ease(0.3) - 0.3).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,267 @@
+use std::io;
+use lodepng;
+use rgb::RGBA;
+use image::{exr, pfm};
+use renderable::HitResult;
+use vector3::{ZERO, Vector3};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum AovFormat
+{
+    Png,
+    Pfm,
+    Exr
+}
+
+#[derive(Clone, Copy)]
+struct AovPixel
+{
+    position: Vector3,
+    normal: Vector3,
+    depth: f64,
+    albedo: Vector3,
+    hits: usize,
+    luminance: f64,
+    luminance_sqr: f64,
+    samples: usize,
+    material: Option<usize>,
+    object: Option<usize>
+}
+
+const EMPTY_PIXEL: AovPixel = AovPixel
+{
+    position: ZERO,
+    normal: ZERO,
+    depth: 0.0,
+    albedo: ZERO,
+    hits: 0,
+    luminance: 0.0,
+    luminance_sqr: 0.0,
+    samples: 0,
+    material: None,
+    object: None
+};
+
+// First hit buffers written alongside the beauty pass. Continuous values are averaged over every camera ray in
+// the pixel that hit something, while the IDs are taken from the first such ray. The variance of each pixel's
+// mean luminance is tracked as well, for the denoiser.
+pub struct Aovs
+{
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<AovPixel>
+}
+
+// AOVs aren't filtered, so unlike film tiles these don't overlap.
+pub struct AovTile
+{
+    x0: usize,
+    y0: usize,
+    width: usize,
+    pixels: Vec<AovPixel>
+}
+
+impl Aovs
+{
+    pub fn new(width: usize, height: usize) -> Aovs
+    {
+        Aovs { width: width, height: height, pixels: vec![EMPTY_PIXEL; width * height] }
+    }
+
+    pub fn tile(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> AovTile
+    {
+        AovTile { x0: x0, y0: y0, width: x1 - x0, pixels: vec![EMPTY_PIXEL; (x1 - x0) * (y1 - y0)] }
+    }
+
+    pub fn merge(&mut self, tile: AovTile)
+    {
+        for (i, pixel) in tile.pixels.iter().enumerate()
+        {
+            let x = tile.x0 + i % tile.width;
+            let y = tile.y0 + i / tile.width;
+            self.pixels[y * self.width + x] = *pixel;
+        }
+    }
+
+    pub fn position(&self) -> Vec<Vector3>
+    {
+        self.pixels.iter().map(|p| if p.hits > 0 { p.position / p.hits as f64 } else { ZERO }).collect()
+    }
+
+    pub fn normal(&self) -> Vec<Vector3>
+    {
+        self.pixels.iter().map(|p| if p.hits > 0 && p.normal.length_sqr() > 0.0 { p.normal.normalized() } else { ZERO }).collect()
+    }
+
+    pub fn depth(&self) -> Vec<f64>
+    {
+        self.pixels.iter().map(|p| if p.hits > 0 { p.depth / p.hits as f64 } else { 0.0 }).collect()
+    }
+
+    pub fn albedo(&self) -> Vec<Vector3>
+    {
+        self.pixels.iter().map(|p| if p.hits > 0 { p.albedo / p.hits as f64 } else { ZERO }).collect()
+    }
+
+    pub fn variance(&self) -> Vec<f64>
+    {
+        self.pixels.iter().map(|p|
+        {
+            if p.samples < 2
+            {
+                return 0.0;
+            }
+            let n = p.samples as f64;
+            let mean = p.luminance / n;
+            ((p.luminance_sqr / n - mean * mean) / (n - 1.0)).max(0.0)
+        }).collect()
+    }
+
+    pub fn object_ids(&self) -> Vec<Option<usize>>
+    {
+        self.pixels.iter().map(|p| p.object).collect()
+    }
+
+    pub fn material_ids(&self) -> Vec<Option<usize>>
+    {
+        self.pixels.iter().map(|p| p.material).collect()
+    }
+
+    // Writes one file per AOV next to the beauty image, or a single EXR holding the beauty pass and every AOV as layers.
+    pub fn write(&self, prefix: &str, format: AovFormat, beauty: &[Vector3]) -> io::Result<()>
+    {
+        let (width, height) = (self.width, self.height);
+        let depth = self.depth();
+        let object_ids = id_values(&self.object_ids());
+        let material_ids = id_values(&self.material_ids());
+
+        match format
+        {
+            AovFormat::Png =>
+            {
+                // Positions are scaled to the box around what was hit, and misses are left black.
+                let position = self.position();
+                let hit = |i: usize| self.pixels[i].hits > 0;
+                let (min, max) = bounds(position.iter().enumerate().filter(|&(i, _)| hit(i)).map(|(_, p)| *p));
+                let max_depth = depth.iter().cloned().fold(0.0, f64::max);
+
+                write_png(&format!("{}.position.png", prefix), width, height,
+                    position.iter().enumerate().map(|(i, p)| if hit(i) { (*p - min) / (max - min) } else { ZERO }))?;
+                write_png(&format!("{}.normal.png", prefix), width, height, self.normal().iter().map(|n| 0.5 * *n + Vector3::new(0.5, 0.5, 0.5)))?;
+                write_png(&format!("{}.depth.png", prefix), width, height, depth.iter().map(|d| Vector3::new(1.0, 1.0, 1.0) * (d / max_depth)))?;
+                write_png(&format!("{}.albedo.png", prefix), width, height, self.albedo().iter().map(|a| gamma(*a)))?;
+                write_png(&format!("{}.material_id.png", prefix), width, height, self.material_ids().iter().map(|id| id_color(*id)))?;
+                write_png(&format!("{}.object_id.png", prefix), width, height, self.object_ids().iter().map(|id| id_color(*id)))
+            },
+            AovFormat::Pfm =>
+            {
+                pfm::write_color(&format!("{}.pfm", prefix), width, height, beauty)?;
+                pfm::write_color(&format!("{}.position.pfm", prefix), width, height, &self.position())?;
+                pfm::write_color(&format!("{}.normal.pfm", prefix), width, height, &self.normal())?;
+                pfm::write_gray(&format!("{}.depth.pfm", prefix), width, height, &depth)?;
+                pfm::write_gray(&format!("{}.variance.pfm", prefix), width, height, &self.variance())?;
+                pfm::write_color(&format!("{}.albedo.pfm", prefix), width, height, &self.albedo())?;
+                pfm::write_gray(&format!("{}.material_id.pfm", prefix), width, height, &material_ids)?;
+                pfm::write_gray(&format!("{}.object_id.pfm", prefix), width, height, &object_ids)
+            },
+            AovFormat::Exr =>
+            {
+                let mut channels = vec![
+                    exr::Channel::new("R", beauty.iter().map(|v| v.x as f32).collect()),
+                    exr::Channel::new("G", beauty.iter().map(|v| v.y as f32).collect()),
+                    exr::Channel::new("B", beauty.iter().map(|v| v.z as f32).collect())
+                ];
+                channels.extend(vector_channels("position", ["X", "Y", "Z"], &self.position()));
+                channels.extend(vector_channels("normal", ["X", "Y", "Z"], &self.normal()));
+                channels.extend(vector_channels("albedo", ["R", "G", "B"], &self.albedo()));
+                channels.push(exr::Channel::new("depth.Z", depth.iter().map(|v| *v as f32).collect()));
+                channels.push(exr::Channel::new("variance.Y", self.variance().iter().map(|v| *v as f32).collect()));
+                channels.push(exr::Channel::new("materialId", material_ids.iter().map(|v| *v as f32).collect()));
+                channels.push(exr::Channel::new("objectId", object_ids.iter().map(|v| *v as f32).collect()));
+                exr::write(&format!("{}.exr", prefix), width, height, channels)
+            }
+        }
+    }
+}
+
+impl AovTile
+{
+    pub fn add_sample(&mut self, x: usize, y: usize, hit: Option<(usize, HitResult)>, material: Option<usize>, color: Vector3)
+    {
+        let pixel = &mut self.pixels[(y - self.y0) * self.width + x - self.x0];
+        let luminance = luminance(color);
+        pixel.luminance += luminance;
+        pixel.luminance_sqr += luminance * luminance;
+        pixel.samples += 1;
+        if let Some((object, h)) = hit
+        {
+            pixel.position += h.origin;
+            pixel.normal += h.normal;
+            pixel.depth += h.t;
+            pixel.albedo += h.material.albedo(h);
+            pixel.hits += 1;
+            if pixel.object.is_none()
+            {
+                pixel.object = Some(object);
+                pixel.material = material;
+            }
+        }
+    }
+}
+
+fn vector_channels(layer: &str, names: [&str; 3], data: &[Vector3]) -> Vec<exr::Channel>
+{
+    vec![
+        exr::Channel::new(&format!("{}.{}", layer, names[0]), data.iter().map(|v| v.x as f32).collect()),
+        exr::Channel::new(&format!("{}.{}", layer, names[1]), data.iter().map(|v| v.y as f32).collect()),
+        exr::Channel::new(&format!("{}.{}", layer, names[2]), data.iter().map(|v| v.z as f32).collect())
+    ]
+}
+
+pub fn luminance(color: Vector3) -> f64
+{
+    0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z
+}
+
+// Misses are written as -1 in float formats.
+fn id_values(ids: &[Option<usize>]) -> Vec<f64>
+{
+    ids.iter().map(|id| id.map(|v| v as f64).unwrap_or(-1.0)).collect()
+}
+
+fn id_color(id: Option<usize>) -> Vector3
+{
+    match id
+    {
+        None => ZERO,
+        Some(v) =>
+        {
+            let hash = (v as u32 + 1).wrapping_mul(2654435761);
+            Vector3::new((hash & 0xff) as f64 / 255.0, ((hash >> 8) & 0xff) as f64 / 255.0, ((hash >> 16) & 0xff) as f64 / 255.0)
+        }
+    }
+}
+
+fn bounds<I: Iterator<Item = Vector3>>(data: I) -> (Vector3, Vector3)
+{
+    let mut min = Vector3::new(f64::MAX, f64::MAX, f64::MAX);
+    let mut max = Vector3::new(f64::MIN, f64::MIN, f64::MIN);
+    for v in data
+    {
+        min = Vector3::new(min.x.min(v.x), min.y.min(v.y), min.z.min(v.z));
+        max = Vector3::new(max.x.max(v.x), max.y.max(v.y), max.z.max(v.z));
+    }
+    (min, max)
+}
+
+fn gamma(color: Vector3) -> Vector3
+{
+    Vector3::new(color.x.max(0.0).sqrt(), color.y.max(0.0).sqrt(), color.z.max(0.0).sqrt())
+}
+
+fn write_png<I: Iterator<Item = Vector3>>(path: &str, width: usize, height: usize, data: I) -> io::Result<()>
+{
+    let to_byte = |v: f64| if v.is_finite() { (v.clamp(0.0, 1.0) * 255.0) as u8 } else { 0 };
+    let pixels: Vec<RGBA<u8>> = data.map(|c| RGBA { r: to_byte(c.x), g: to_byte(c.y), b: to_byte(c.z), a: 255 }).collect();
+    lodepng::encode32_file(path, &pixels, width, height).map_err(|e| io::Error::other(e.to_string()))
+}
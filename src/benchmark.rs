@@ -0,0 +1,249 @@
+// Throughput benchmarks for the parts of the renderer that dominate its run time. Each one runs for at least
+// MIN_SECONDS over a fixed set of pregenerated inputs, so that results from different branches are comparable,
+// and reports how many rays it got through per second.
+use std::f64;
+use std::fs::File;
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Instant;
+use rand::{SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+use camera::Camera;
+use distance::{DistanceField, Smooth};
+use distance::domain::Transformed;
+use distance::fractal::{Mandelbulb, Mandelbox, QuaternionJulia};
+use distance::shapes;
+use filter;
+use material::Material;
+use material::dielectric::Dielectric;
+use material::diffuse_light::DiffuseLight;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use random;
+use ray::Ray;
+use renderable::{Renderable, HitResult, Aabb, EPSILON};
+use renderable::cone::Cone;
+use renderable::csg::{Csg, Operation};
+use renderable::cube::Cube;
+use renderable::cylinder::Cylinder;
+use renderable::disk::Disk;
+use renderable::heightfield::Heightfield;
+use renderable::plane::Plane;
+use renderable::plane_bounded::PlaneBounded;
+use renderable::quartic::{Quartic, Term};
+use renderable::sdf::Sdf;
+use renderable::sphere::Sphere;
+use renderable::torus::Torus;
+use renderable::triangle::Triangle;
+use renderer::Renderer;
+use scenes;
+use transform::Transform;
+use vector3::{ZERO, ONE, Vector3};
+
+const MIN_SECONDS: f64 = 0.5;
+const RAY_COUNT: usize = 10000;
+const SCENE_SIZES: [usize; 4] = [10, 100, 1000, 10000];
+
+const FRAME_WIDTH: usize = 64;
+const FRAME_HEIGHT: usize = 48;
+const FRAME_SAMPLES: usize = 4;
+const FRAME_BOUNCES: i32 = 8;
+
+pub struct Measurement
+{
+    pub group: &'static str,
+    pub name: String,
+    pub iterations: usize,
+    pub rays: usize,
+    pub seconds: f64
+}
+
+impl Measurement
+{
+    pub fn rays_per_second(&self) -> f64
+    {
+        self.rays as f64 / self.seconds
+    }
+}
+
+// Repeats a run, which returns how many rays it traced, until enough time has passed to trust the average.
+fn measure<F: FnMut() -> usize>(group: &'static str, name: &str, mut run: F) -> Measurement
+{
+    run();
+    let start = Instant::now();
+    let mut iterations = 0;
+    let mut rays = 0;
+    while iterations == 0 || start.elapsed().as_secs_f64() < MIN_SECONDS
+    {
+        rays += run();
+        iterations += 1;
+    }
+
+    let measurement = Measurement { group: group, name: name.to_string(), iterations: iterations, rays: rays, seconds: start.elapsed().as_secs_f64() };
+    println!("{:<10} {:<24} {:>14.0} rays/s", group, name, measurement.rays_per_second());
+    measurement
+}
+
+fn random_vector(rng: &mut SmallRng, scale: f64) -> Vector3
+{
+    Vector3::new(rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5) * (2.0 * scale)
+}
+
+// Rays from all around the origin, half of them aimed close enough to it to hit most of the shapes benchmarked.
+fn random_rays(count: usize) -> Vec<Ray>
+{
+    let mut rng = SmallRng::seed_from_u64(1);
+    (0..count).map(|i|
+    {
+        let origin = random_vector(&mut rng, 8.0);
+        let target = if i % 2 == 0 { random_vector(&mut rng, 0.5) } else { random_vector(&mut rng, 8.0) };
+        Ray { origin: origin, direction: (target - origin).normalized(), time: 0.0 }
+    }).collect()
+}
+
+// Shrinks a fractal reaching out to the given radius down to a unit sphere, inside a box, the way the scenes place them.
+fn fractal<F: DistanceField + 'static>(field: F, radius: f64, material: Lambert) -> Sdf
+{
+    let mut sdf = Sdf::new(Transformed::new(field, Transform::new(ZERO, ZERO, 1.0 / radius)), material);
+    sdf.precision = 2e-4;
+    sdf.bounds = Some(Aabb::new(-ONE, ONE));
+    sdf
+}
+
+// Rolling hills over a 2 by 2 square, with enough samples for the quadtree to matter.
+fn hills(material: Lambert) -> Heightfield
+{
+    let samples = 129;
+    let heights = (0..samples * samples).map(|i|
+    {
+        let (x, z) = ((i % samples) as f64 / 8.0, (i / samples) as f64 / 8.0);
+        0.5 + 0.25 * (x.sin() + z.cos())
+    }).collect();
+    Heightfield::new(samples, samples, heights, Vector3::new(-1.0, -0.5, -1.0), Vector3::new(2.0, 1.0, 2.0), material)
+}
+
+fn renderables(rays: &[Ray]) -> Vec<Measurement>
+{
+    let material = Lambert::new(ONE);
+    let renderables: Vec<(&str, Box<Renderable>)> = vec![
+        ("sphere", Box::new(Sphere::new(ZERO, 1.0, material.clone()))),
+        ("cube", Box::new(Cube::new(ZERO, 2.0, 1.5, 1.0, material.clone()))),
+        ("plane", Box::new(Plane::new(ZERO, Vector3::new(0.2, 1.0, 0.1), material.clone()))),
+        ("plane_bounded", Box::new(PlaneBounded::new(ZERO, Vector3::new(0.2, 1.0, 0.1), 2.0, 2.0, material.clone()))),
+        ("triangle", Box::new(Triangle::new(Vector3::new(-1.0, -1.0, 0.0), Vector3::new(1.0, -1.0, 0.0), Vector3::new(0.0, 1.0, 0.3), material.clone()))),
+        ("cylinder", Box::new(Cylinder::new(Vector3::new(0.0, -1.0, 0.0), Vector3::new(0.2, 1.0, 0.1), 0.8, true, material.clone()))),
+        ("cone", Box::new(Cone::new(Vector3::new(0.0, -1.0, 0.0), Vector3::new(0.2, 1.0, 0.1), 1.0, 0.3, true, material.clone()))),
+        ("disk", Box::new(Disk::new(ZERO, Vector3::new(0.2, 1.0, 0.1), 1.0, material.clone()))),
+        ("annulus", Box::new(Disk::annulus(ZERO, Vector3::new(0.2, 1.0, 0.1), 0.5, 1.0, material.clone()))),
+        ("torus", Box::new(Torus::new(ZERO, Vector3::new(0.2, 1.0, 0.1), 0.8, 0.3, material.clone()))),
+        // x⁴ + y⁴ + z⁴ = 1, a rounded cube.
+        ("quartic", Box::new(Quartic::new(vec![Term::new(1.0, 4, 0, 0), Term::new(1.0, 0, 4, 0), Term::new(1.0, 0, 0, 4), Term::new(-1.0, 0, 0, 0)],
+            Aabb::new(Vector3::new(-1.01, -1.01, -1.01), Vector3::new(1.01, 1.01, 1.01)), material.clone()))),
+        ("csg", Box::new(Csg::difference(Cube::new(ZERO, 1.5, 1.5, 1.5, material.clone()), Sphere::new(ZERO, 1.0, material.clone())))),
+        ("sdf", Box::new(Sdf::new(Smooth::new(Operation::Union, shapes::Sphere::new(ZERO, 0.8), shapes::Torus::new(ZERO, 1.0, 0.2), 0.2), material.clone()))),
+        ("mandelbulb", Box::new(fractal(Mandelbulb::new(8.0, 12), 1.2, material.clone()))),
+        ("mandelbox", Box::new(fractal(Mandelbox::new(2.0, 12), 6.0 * 3.0f64.sqrt(), material.clone()))),
+        ("quaternion_julia", Box::new(fractal(QuaternionJulia::new([-0.2, 0.6, 0.2, 0.2], 12), 1.2, material.clone()))),
+        ("heightfield", Box::new(hills(material)))
+    ];
+
+    renderables.iter().map(|&(name, ref renderable)| measure("renderable", name, ||
+    {
+        for ray in rays
+        {
+            black_box(renderable.test_hit(black_box(*ray), EPSILON, f64::MAX));
+        }
+        rays.len()
+    })).collect()
+}
+
+fn scene_sizes() -> Vec<Measurement>
+{
+    SCENE_SIZES.iter().map(|&size|
+    {
+        // Camera rays into the triangle soup, so that most of them have something to hit.
+        let (scene, camera) = scenes::triangle_soup::build_with_count(1.0, size);
+        let mut rng = SmallRng::seed_from_u64(1);
+        let rays: Vec<Ray> = (0..RAY_COUNT / 10).filter_map(|_| camera.get_ray(rng.random::<f64>(), rng.random::<f64>())).collect();
+        measure("scene", &format!("triangles_{}", size), ||
+        {
+            for ray in &rays
+            {
+                black_box(scene.test_hit(black_box(*ray)));
+            }
+            rays.len()
+        })
+    }).collect()
+}
+
+fn materials(rays: &[Ray]) -> Vec<Measurement>
+{
+    let materials: Vec<(&str, Box<Material>)> = vec![
+        ("lambert", Box::new(Lambert::new(Vector3::new(0.5, 0.5, 0.5)))),
+        ("metal", Box::new(Metal::new(Vector3::new(0.8, 0.8, 0.8), 0.3))),
+        ("dielectric", Box::new(Dielectric::new(1.5))),
+        ("diffuse_light", Box::new(DiffuseLight::new(ONE)))
+    ];
+
+    // The rays come from every direction, so half of them arrive from behind the surface, as they would inside glass.
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    random::seed(1);
+    materials.iter().map(|&(name, ref material)| measure("material", name, ||
+    {
+        for ray in rays
+        {
+            let hit = HitResult { origin: ZERO, normal: normal, t: 1.0, time: 0.0, uv: (0.0, 0.0), material: &**material };
+            black_box(material.scatter(black_box(*ray), hit));
+        }
+        rays.len()
+    })).collect()
+}
+
+fn frames() -> Vec<Measurement>
+{
+    let aspect = FRAME_WIDTH as f64 / FRAME_HEIGHT as f64;
+    let mut measurements = Vec::new();
+    for name in scenes::NAMES.iter()
+    {
+        let (scene, camera) = scenes::build(name, aspect).unwrap();
+        let mut renderer = Renderer::new(FRAME_WIDTH, FRAME_HEIGHT, FRAME_SAMPLES, FRAME_BOUNCES);
+        renderer.seed = 1;
+        measurements.push(measure("frame", name, ||
+        {
+            black_box(renderer.render(&scene, &camera, filter::from_name("mitchell", None).unwrap()));
+            FRAME_WIDTH * FRAME_HEIGHT * FRAME_SAMPLES
+        }));
+    }
+    measurements
+}
+
+pub fn run() -> Vec<Measurement>
+{
+    let rays = random_rays(RAY_COUNT);
+    let mut measurements = renderables(&rays);
+    measurements.extend(scene_sizes());
+    measurements.extend(materials(&rays));
+    measurements.extend(frames());
+    measurements
+}
+
+// Rays per second are the ones handed to test_hit or scatter, or camera rays for whole frames.
+pub fn write_json(path: &str, measurements: &[Measurement]) -> io::Result<()>
+{
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let mut file = File::create(path)?;
+    writeln!(file, "{{")?;
+    writeln!(file, "  \"threads\": {},", threads)?;
+    writeln!(file, "  \"frame\": {{ \"width\": {}, \"height\": {}, \"samples\": {}, \"bounces\": {} }},",
+        FRAME_WIDTH, FRAME_HEIGHT, FRAME_SAMPLES, FRAME_BOUNCES)?;
+    writeln!(file, "  \"benchmarks\": [")?;
+    for (i, m) in measurements.iter().enumerate()
+    {
+        let separator = if i + 1 < measurements.len() { "," } else { "" };
+        writeln!(file, "    {{ \"group\": \"{}\", \"name\": \"{}\", \"iterations\": {}, \"rays\": {}, \"seconds\": {:.6}, \"rays_per_second\": {:.1} }}{}",
+            m.group, m.name, m.iterations, m.rays, m.seconds, m.rays_per_second(), separator)?;
+    }
+    writeln!(file, "  ]")?;
+    writeln!(file, "}}")
+}
@@ -0,0 +1,74 @@
+use std::f64::consts::PI;
+use lodepng;
+use random;
+use vector3::Vector3;
+use camera::random_in_unit_disk;
+
+// The shape of the lens opening, which out of focus highlights take on. Points on it are sampled within the unit
+// disk, and scaled by the lens radius.
+#[derive(Clone)]
+pub enum Aperture
+{
+    Circle,
+    // A regular polygon with a corner at the rotation in degrees counter-clockwise from the right, like the opening
+    // between the blades of an iris diaphragm.
+    Polygon { blades: usize, rotation: f64 },
+    // An image stretched over the square around the unit disk, letting light through in proportion to how bright
+    // it is.
+    Mask { width: usize, height: usize, weights: Vec<f64> }
+}
+
+impl Aperture
+{
+    pub fn load_mask(path: &str) -> Result<Aperture, String>
+    {
+        let image = lodepng::decode32_file(path).map_err(|e| format!("Couldn't load aperture mask {}: {}", path, e))?;
+        let weights: Vec<f64> = image.buffer.iter()
+            .map(|p| (p.r as f64 + p.g as f64 + p.b as f64) / (3.0 * 255.0) * (p.a as f64 / 255.0))
+            .collect();
+
+        let max = weights.iter().cloned().fold(0.0, f64::max);
+        if max == 0.0
+        {
+            return Err(format!("Aperture mask {} doesn't let any light through", path));
+        }
+
+        Ok(Aperture::Mask { width: image.width, height: image.height, weights: weights.iter().map(|w| w / max).collect() })
+    }
+
+    pub fn sample(&self) -> Vector3
+    {
+        match *self
+        {
+            Aperture::Circle => random_in_unit_disk(),
+            Aperture::Polygon { blades, rotation } =>
+            {
+                // Pick one of the triangles between the center and an edge, then a uniform point within it.
+                let blade = ((random::next_f64() * blades as f64) as usize).min(blades - 1);
+                let angle = |i: usize| rotation.to_radians() + 2.0 * PI * i as f64 / blades as f64;
+                let a = Vector3::new(angle(blade).cos(), angle(blade).sin(), 0.0);
+                let b = Vector3::new(angle(blade + 1).cos(), angle(blade + 1).sin(), 0.0);
+                let (mut s, mut t) = (random::next_f64(), random::next_f64());
+                if s + t > 1.0
+                {
+                    s = 1.0 - s;
+                    t = 1.0 - t;
+                }
+                s * a + t * b
+            },
+            Aperture::Mask { width, height, ref weights } =>
+            {
+                loop
+                {
+                    let (x, y) = (random::next_f64(), random::next_f64());
+                    let px = ((x * width as f64) as usize).min(width - 1);
+                    let py = (((1.0 - y) * height as f64) as usize).min(height - 1);
+                    if random::next_f64() < weights[py * width + px]
+                    {
+                        return Vector3::new(2.0 * x - 1.0, 2.0 * y - 1.0, 0.0);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::f64::consts::PI;
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+
+// A full 360 by 180 degree panorama in the latitude-longitude layout environment maps use, with the view direction
+// in the center of the image. Meant for images twice as wide as they are high.
+pub struct Equirectangular
+{
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3
+}
+
+impl Equirectangular
+{
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3) -> Equirectangular
+    {
+        let (u, v, w) = basis(origin, look_at, v_up);
+        Equirectangular { origin: origin, u: u, v: v, w: w }
+    }
+}
+
+impl Camera for Equirectangular
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        let longitude = (u - 0.5) * 2.0 * PI;
+        let latitude = (v - 0.5) * PI;
+        let direction = latitude.cos() * (longitude.sin() * self.u - longitude.cos() * self.w) + latitude.sin() * self.v;
+        Some(Ray { origin: self.origin, direction: direction, time: 0.0 })
+    }
+}
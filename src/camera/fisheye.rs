@@ -0,0 +1,66 @@
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+
+// How the angle from the view direction maps to the distance from the center of the image circle.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FisheyeMapping
+{
+    // Distance grows linearly with the angle.
+    Equidistant,
+    // Areas on the image are proportional to solid angles, which is what most real fisheye lenses approximate.
+    Equisolid
+}
+
+// A circular fisheye, with an image circle that fits the shorter side of the image and spans fov degrees. Outside
+// the circle there's nothing to see.
+pub struct Fisheye
+{
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3,
+    pub fov: f64,
+    pub aspect: f64,
+    pub mapping: FisheyeMapping
+}
+
+impl Fisheye
+{
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3, fov: f64, aspect: f64, mapping: FisheyeMapping) -> Fisheye
+    {
+        let (u, v, w) = basis(origin, look_at, v_up);
+        Fisheye { origin: origin, u: u, v: v, w: w, fov: fov, aspect: aspect, mapping: mapping }
+    }
+}
+
+impl Camera for Fisheye
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        let (x, y) = if self.aspect >= 1.0
+        {
+            ((2.0 * u - 1.0) * self.aspect, 2.0 * v - 1.0)
+        }
+        else
+        {
+            (2.0 * u - 1.0, (2.0 * v - 1.0) / self.aspect)
+        };
+
+        let radius = (x * x + y * y).sqrt();
+        if radius > 1.0
+        {
+            return None;
+        }
+
+        let half_fov = self.fov.to_radians() / 2.0;
+        let theta = match self.mapping
+        {
+            FisheyeMapping::Equidistant => radius * half_fov,
+            FisheyeMapping::Equisolid => 2.0 * (radius * (half_fov / 2.0).sin()).asin()
+        };
+        let phi = y.atan2(x);
+        let direction = theta.sin() * (phi.cos() * self.u + phi.sin() * self.v) - theta.cos() * self.w;
+        Some(Ray { origin: self.origin, direction: direction, time: 0.0 })
+    }
+}
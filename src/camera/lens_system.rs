@@ -0,0 +1,344 @@
+use std::f64;
+use std::fs;
+use rand::{SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+use random;
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+
+// A 50mm f/2 double Gauss lens, from US patent 2,673,491, as scaled in Modern Lens Design.
+pub const DOUBLE_GAUSS_50MM: &str = "
+# radius  thickness  ior    aperture
+29.475    3.76       1.67   25.2
+84.83     0.12       1      25.2
+19.275    4.025      1.67   23
+40.77     3.275      1.699  23
+12.75     5.705      1      18
+0         4.5        0      17.1
+-14.495   1.18       1.603  17
+40.77     6.065      1.658  20
+-20.385   0.19       1      20
+437.065   3.22       1.717  20
+-39.73    40         1      20
+";
+
+const PUPIL_BINS: usize = 64;
+const PUPIL_SAMPLES: usize = 64;
+
+// One surface of a lens prescription, listed from the front of the lens to the back. The radius of curvature is
+// positive when its center is behind the surface, and zero for the flat aperture stop. Thickness is the distance
+// to the next surface along the axis, and the index of refraction is that of the glass (or air, given as 0 or 1)
+// up to it. All lengths are in millimeters, with the aperture being the surface's diameter.
+#[derive(Clone, Copy)]
+pub struct LensElement
+{
+    pub radius: f64,
+    pub thickness: f64,
+    pub ior: f64,
+    pub aperture: f64
+}
+
+impl LensElement
+{
+    fn ior(&self) -> f64
+    {
+        if self.ior == 0.0 { 1.0 } else { self.ior }
+    }
+}
+
+// Reads a prescription table with a surface per line, giving its radius, thickness, index of refraction and aperture
+// in that order. Anything after a # is a comment.
+pub fn parse_prescription(text: &str) -> Result<Vec<LensElement>, String>
+{
+    let mut elements = Vec::new();
+    for (number, line) in text.lines().enumerate()
+    {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty()
+        {
+            continue;
+        }
+
+        let values: Vec<f64> = line.split_whitespace().map(|v| v.parse().map_err(|_| format!("Line {}: {} isn't a number", number + 1, v)))
+            .collect::<Result<_, _>>()?;
+        if values.len() != 4
+        {
+            return Err(format!("Line {}: expected radius, thickness, ior and aperture", number + 1));
+        }
+        elements.push(LensElement { radius: values[0], thickness: values[1], ior: values[2], aperture: values[3] });
+    }
+
+    if elements.is_empty()
+    {
+        return Err(String::from("The prescription has no surfaces"));
+    }
+    Ok(elements)
+}
+
+pub fn load_prescription(path: &str) -> Result<Vec<LensElement>, String>
+{
+    let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read lens prescription {}: {}", path, e))?;
+    parse_prescription(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+// Traces rays from the film through every surface of a real lens, so that distortion, vignetting, field curvature
+// and focus breathing all come from the lens itself. Inside, the optical axis is z, with the film at z = 0 and the
+// lens in front of it at negative z, and lengths are in millimeters. The scene is taken to be in meters.
+pub struct LensSystem
+{
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3,
+    pub elements: Vec<LensElement>,
+    pub film_width: f64,
+    pub film_height: f64,
+    // Bounds on the rear surface's plane, for film points at increasing distances from the axis, outside which no
+    // ray makes it through the lens. Sampling only within them wastes fewer rays.
+    pupil_bounds: Vec<(f64, f64, f64, f64)>,
+    // How much of the bounds lets light through for a film point on the axis.
+    axial_transmission: f64
+}
+
+impl LensSystem
+{
+    // Focuses on a point focus_distance in front of the film, in scene units.
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3, elements: Vec<LensElement>, film_width: f64, aspect: f64, focus_distance: f64) -> LensSystem
+    {
+        let (u, v, w) = basis(origin, look_at, v_up);
+        let mut lens = LensSystem
+        {
+            origin: origin,
+            u: u,
+            v: v,
+            w: w,
+            elements: elements,
+            film_width: film_width,
+            film_height: film_width / aspect,
+            pupil_bounds: Vec::new(),
+            axial_transmission: 1.0
+        };
+        lens.focus(focus_distance * 1000.0);
+        lens
+    }
+
+    fn rear_z(&self) -> f64
+    {
+        -self.elements.last().unwrap().thickness
+    }
+
+    // Distance from the rear surface to the front one.
+    fn length(&self) -> f64
+    {
+        let n = self.elements.len();
+        self.elements[..n - 1].iter().map(|e| e.thickness).sum()
+    }
+
+    // Follows a ray through the lens, in either direction, returning it as it leaves or None if it hits the barrel or
+    // the stop, or is reflected.
+    fn trace(&self, ray: Ray) -> Option<Ray>
+    {
+        let from_film = ray.direction.z < 0.0;
+        let mut ray = ray;
+        let n = self.elements.len();
+        let mut z = if from_film { self.rear_z() } else { self.rear_z() - self.length() };
+
+        for step in 0..n
+        {
+            let i = if from_film { n - 1 - step } else { step };
+            let element = self.elements[i];
+            let t;
+            let mut normal = Vector3::new(0.0, 0.0, 1.0);
+            if element.radius == 0.0
+            {
+                t = (z - ray.origin.z) / ray.direction.z;
+            }
+            else
+            {
+                let center = Vector3::new(0.0, 0.0, z + element.radius);
+                let offset = ray.origin - center;
+                let a = ray.direction.length_sqr();
+                let b = offset.dot(ray.direction);
+                let c = offset.length_sqr() - element.radius * element.radius;
+                let discriminant = b * b - a * c;
+                if discriminant < 0.0
+                {
+                    return None;
+                }
+
+                // Of the sphere's two sides, the surface is the one facing the vertex.
+                let closer = (element.radius > 0.0) == (ray.direction.z > 0.0);
+                t = if closer { (-b - discriminant.sqrt()) / a } else { (-b + discriminant.sqrt()) / a };
+                normal = (ray.translate_to(t) - center).normalized();
+            }
+
+            if t <= 0.0
+            {
+                return None;
+            }
+            let hit = ray.translate_to(t);
+            if hit.x * hit.x + hit.y * hit.y > element.aperture * element.aperture / 4.0
+            {
+                return None;
+            }
+
+            if element.radius != 0.0
+            {
+                let outside = if i == 0 { 1.0 } else { self.elements[i - 1].ior() };
+                let (from, to) = if from_film { (element.ior(), outside) } else { (outside, element.ior()) };
+                let direction = refract(ray.direction.normalized(), normal, from / to)?;
+                ray = Ray { origin: hit, direction: direction, time: ray.time };
+            }
+            else
+            {
+                ray = Ray { origin: hit, direction: ray.direction, time: ray.time };
+            }
+
+            if from_film
+            {
+                z -= if i > 0 { self.elements[i - 1].thickness } else { 0.0 };
+            }
+            else
+            {
+                z += element.thickness;
+            }
+        }
+
+        Some(ray)
+    }
+
+    // Finds where a ray leaving an on-axis point focus_distance in front of the film, close to the axis, comes back
+    // to it behind the lens.
+    fn image_z(&self, focus_distance: f64) -> Option<f64>
+    {
+        let front = self.rear_z() - self.length();
+        let height = self.elements[0].aperture * 0.01;
+        let object = Vector3::new(0.0, 0.0, -focus_distance);
+        let ray = self.trace(Ray { origin: object, direction: Vector3::new(0.0, height, front + focus_distance), time: 0.0 })?;
+        if ray.direction.y >= 0.0
+        {
+            return None;
+        }
+        Some(ray.origin.z - ray.origin.y / ray.direction.y * ray.direction.z)
+    }
+
+    // Autofocus, which moves the whole lens along the axis until a point focus_distance millimeters in front of the
+    // film is imaged onto it.
+    pub fn focus(&mut self, focus_distance: f64)
+    {
+        // Moving the lens away from the film moves the image less far, so bisect on the distance between them.
+        let last = self.elements.len() - 1;
+        let (mut near, mut far) = (0.0, focus_distance - self.length());
+        for _ in 0..64
+        {
+            let gap = (near + far) / 2.0;
+            self.elements[last].thickness = gap;
+            // Without an image, the point is too close to the lens for it to focus, so it needs to move back.
+            match self.image_z(focus_distance)
+            {
+                Some(z) if z > 0.0 => near = gap,
+                _ => far = gap
+            }
+        }
+        self.elements[last].thickness = (near + far) / 2.0;
+        self.compute_pupil_bounds();
+    }
+
+    fn compute_pupil_bounds(&mut self)
+    {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let rear = self.elements.last().unwrap();
+        let (rear_z, extent) = (self.rear_z(), rear.aperture / 2.0 * 1.5);
+        let max_radius = (self.film_width * self.film_width + self.film_height * self.film_height).sqrt() / 2.0;
+
+        self.pupil_bounds = (0..PUPIL_BINS).map(|bin|
+        {
+            let film = Vector3::new(max_radius * bin as f64 / (PUPIL_BINS - 1) as f64, 0.0, 0.0);
+            let mut bounds = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+            for _ in 0..PUPIL_SAMPLES * PUPIL_SAMPLES
+            {
+                let x = (rng.random::<f64>() * 2.0 - 1.0) * extent;
+                let y = (rng.random::<f64>() * 2.0 - 1.0) * extent;
+                if self.trace(Ray { origin: film, direction: Vector3::new(x, y, rear_z) - film, time: 0.0 }).is_some()
+                {
+                    bounds = (bounds.0.min(x), bounds.1.min(y), bounds.2.max(x), bounds.3.max(y));
+                }
+            }
+            bounds
+        }).collect();
+
+        // Sampled points may miss the very edges of the pupil, so leave a little room.
+        for b in self.pupil_bounds.iter_mut()
+        {
+            if b.0 <= b.2
+            {
+                let margin = 2.0 * extent / PUPIL_SAMPLES as f64;
+                *b = (b.0 - margin, b.1 - margin, b.2 + margin, b.3 + margin);
+            }
+        }
+
+        let (x0, y0, x1, y1) = self.pupil_bounds[0];
+        let passed = (0..PUPIL_SAMPLES * PUPIL_SAMPLES).filter(|_|
+        {
+            let target = Vector3::new(x0 + (x1 - x0) * rng.random::<f64>(), y0 + (y1 - y0) * rng.random::<f64>(), rear_z);
+            self.trace(Ray { origin: Vector3::new(0.0, 0.0, 0.0), direction: target, time: 0.0 }).is_some()
+        }).count();
+        self.axial_transmission = (passed as f64 / (PUPIL_SAMPLES * PUPIL_SAMPLES) as f64).max(1e-6);
+    }
+
+    fn pupil_area(bounds: (f64, f64, f64, f64)) -> f64
+    {
+        if bounds.0 > bounds.2 { 0.0 } else { (bounds.2 - bounds.0) * (bounds.3 - bounds.1) }
+    }
+}
+
+impl Camera for LensSystem
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        self.get_weighted_ray(u, v).map(|(ray, _)| ray)
+    }
+
+    // Rays through the lens carry the cosine to the fourth falloff of light reaching the film at an angle, and the
+    // size of the pupil they were sampled from, scaled so that the middle of the image has a weight of one.
+    fn get_weighted_ray(&self, u: f64, v: f64) -> Option<(Ray, f64)>
+    {
+        // The lens flips the image, so the top right of the image is at the bottom left of the film.
+        let film = Vector3::new(-(u - 0.5) * self.film_width, -(v - 0.5) * self.film_height, 0.0);
+        let radius = (film.x * film.x + film.y * film.y).sqrt();
+        let max_radius = (self.film_width * self.film_width + self.film_height * self.film_height).sqrt() / 2.0;
+        let bin = ((radius / max_radius * (PUPIL_BINS - 1) as f64).round() as usize).min(PUPIL_BINS - 1);
+        let bounds = self.pupil_bounds[bin];
+        if bounds.0 > bounds.2
+        {
+            return None;
+        }
+
+        // Bounds were found for film points along x, so turn the sampled point around to where this one is.
+        let local = Vector3::new(bounds.0 + (bounds.2 - bounds.0) * random::next_f64(), bounds.1 + (bounds.3 - bounds.1) * random::next_f64(), 0.0);
+        let (sin, cos) = if radius > 0.0 { (film.y / radius, film.x / radius) } else { (0.0, 1.0) };
+        let target = Vector3::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos, self.rear_z());
+        let direction = (target - film).normalized();
+        let ray = self.trace(Ray { origin: film, direction: direction, time: 0.0 })?;
+
+        let cos4 = direction.z * direction.z * direction.z * direction.z;
+        let weight = cos4 * LensSystem::pupil_area(bounds) / LensSystem::pupil_area(self.pupil_bounds[0]) / self.axial_transmission;
+        let to_world = |p: Vector3| p.x * self.u + p.y * self.v + p.z * self.w;
+        Some((Ray { origin: self.origin + to_world(ray.origin) / 1000.0, direction: to_world(ray.direction), time: 0.0 }, weight))
+    }
+}
+
+// Bends a unit direction through a surface with the given normal, which may face either way, going from a medium
+// with eta times the index of refraction of the one it enters.
+fn refract(direction: Vector3, normal: Vector3, eta: f64) -> Option<Vector3>
+{
+    let normal = if direction.dot(normal) > 0.0 { -normal } else { normal };
+    let cos_i = -direction.dot(normal);
+    let sin2_t = eta * eta * (1.0 - cos_i * cos_i);
+    if sin2_t > 1.0
+    {
+        return None;
+    }
+    Some(eta * direction + (eta * cos_i - (1.0 - sin2_t).sqrt()) * normal)
+}
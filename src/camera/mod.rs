@@ -0,0 +1,100 @@
+pub mod aperture;
+pub mod perspective;
+pub mod physical;
+pub mod orthographic;
+pub mod fisheye;
+pub mod equirectangular;
+pub mod stereo;
+pub mod ods;
+pub mod lens_system;
+pub mod shutter;
+pub mod path;
+#[cfg(test)]
+mod tests;
+
+use random;
+use ray::Ray;
+use vector3::Vector3;
+use camera::equirectangular::Equirectangular;
+use camera::fisheye::{Fisheye, FisheyeMapping};
+use camera::lens_system::{LensElement, LensSystem};
+use camera::orthographic::Orthographic;
+use camera::perspective::Perspective;
+
+pub const NAMES: [&str; 6] = ["perspective", "orthographic", "fisheye", "fisheye_equisolid", "equirectangular", "lens"];
+
+pub trait Camera: Sync
+{
+    // Gets the ray through a point on the image, from (0, 0) at the bottom left to (1, 1) at the top right, or None
+    // where the image isn't covered by the camera's projection.
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>;
+
+    // Like get_ray, along with how much of the light coming back along the ray reaches the image, for cameras where
+    // that varies across it.
+    fn get_weighted_ray(&self, u: f64, v: f64) -> Option<(Ray, f64)>
+    {
+        self.get_ray(u, v).map(|ray| (ray, 1.0))
+    }
+}
+
+// Settings only some kinds of camera use.
+#[derive(Clone)]
+pub struct CameraOptions
+{
+    // Field of view in degrees for fisheye cameras.
+    pub fov: f64,
+    // The lens system camera's prescription, and the width of its film in millimeters.
+    pub lens: Vec<LensElement>,
+    pub film_width: f64
+}
+
+impl CameraOptions
+{
+    pub fn new() -> CameraOptions
+    {
+        CameraOptions { fov: 180.0, lens: lens_system::parse_prescription(lens_system::DOUBLE_GAUSS_50MM).unwrap(), film_width: 36.0 }
+    }
+}
+
+// Builds a camera of the given kind that stands where a scene's perspective camera does, and looks the same way. The
+// orthographic camera shows what fits in the perspective camera's view at its focus distance, and the lens system
+// is focused at that distance.
+pub fn from_name(name: &str, perspective: Perspective, options: &CameraOptions, aspect: f64) -> Option<Box<Camera>>
+{
+    let look_at = perspective.origin - perspective.w;
+    match name
+    {
+        "perspective" => Some(Box::new(perspective)),
+        "orthographic" =>
+        {
+            let height = 2.0 * (perspective.fov.to_radians() / 2.0).tan() * perspective.focus_distance;
+            Some(Box::new(Orthographic::new(perspective.origin, look_at, perspective.v, height, aspect)))
+        },
+        "fisheye" => Some(Box::new(Fisheye::new(perspective.origin, look_at, perspective.v, options.fov, aspect, FisheyeMapping::Equidistant))),
+        "fisheye_equisolid" => Some(Box::new(Fisheye::new(perspective.origin, look_at, perspective.v, options.fov, aspect, FisheyeMapping::Equisolid))),
+        "equirectangular" => Some(Box::new(Equirectangular::new(perspective.origin, look_at, perspective.v))),
+        "lens" => Some(Box::new(LensSystem::new(perspective.origin, look_at, perspective.v, options.lens.clone(), options.film_width, aspect, perspective.focus_distance))),
+        _ => None
+    }
+}
+
+// An orthonormal basis for a camera looking from origin to look_at, where u points right, v up, and w backwards.
+fn basis(origin: Vector3, look_at: Vector3, v_up: Vector3) -> (Vector3, Vector3, Vector3)
+{
+    let w = (origin - look_at).normalized();
+    let u = v_up.cross(w).normalized();
+    let v = w.cross(u);
+    (u, v, w)
+}
+
+fn random_in_unit_disk() -> Vector3
+{
+    loop
+    {
+        let p = 2.0 * Vector3{x: random::next_f64(), y: random::next_f64(), z: 0.0} - Vector3{x: 1.0, y: 1.0, z: 0.0};
+        if p.dot(p) < 1.0
+        {
+            return p;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::f64::consts::PI;
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+use camera::stereo::Eye;
+
+// Omni-directional stereo: an equirectangular panorama for one eye, where every column of the image is seen from
+// where that eye would be with the head turned to face it. Eyes sit on a circle interocular wide, so any view
+// direction gets the right parallax for looking that way.
+pub struct OmniStereo
+{
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3,
+    pub interocular: f64,
+    pub eye: Eye
+}
+
+impl OmniStereo
+{
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3, interocular: f64, eye: Eye) -> OmniStereo
+    {
+        let (u, v, w) = basis(origin, look_at, v_up);
+        OmniStereo { origin: origin, u: u, v: v, w: w, interocular: interocular, eye: eye }
+    }
+}
+
+impl Camera for OmniStereo
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        let longitude = (u - 0.5) * 2.0 * PI;
+        let latitude = (v - 0.5) * PI;
+        let direction = latitude.cos() * (longitude.sin() * self.u - longitude.cos() * self.w) + latitude.sin() * self.v;
+        let right = longitude.cos() * self.u + longitude.sin() * self.w;
+        let side = if self.eye == Eye::Left { -1.0 } else { 1.0 };
+        Some(Ray { origin: self.origin + right * (side * self.interocular / 2.0), direction: direction, time: 0.0 })
+    }
+}
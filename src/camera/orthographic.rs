@@ -0,0 +1,32 @@
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+
+// Parallel rays through a rectangle of the given height around the origin, so that sizes don't change with distance.
+pub struct Orthographic
+{
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3,
+    pub width: f64,
+    pub height: f64
+}
+
+impl Orthographic
+{
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3, height: f64, aspect: f64) -> Orthographic
+    {
+        let (u, v, w) = basis(origin, look_at, v_up);
+        Orthographic { origin: origin, u: u, v: v, w: w, width: height * aspect, height: height }
+    }
+}
+
+impl Camera for Orthographic
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        let origin = self.origin + (u - 0.5) * self.width * self.u + (v - 0.5) * self.height * self.v;
+        Some(Ray { origin: origin, direction: -self.w, time: 0.0 })
+    }
+}
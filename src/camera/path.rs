@@ -0,0 +1,25 @@
+use animation::Track;
+use vector3::Vector3;
+use camera::perspective::Perspective;
+
+// Where a scene's camera is over time in an animation, and what it looks at.
+pub struct CameraPath
+{
+    pub origin: Track<Vector3>,
+    pub look_at: Track<Vector3>,
+    pub fov: Track<f64>,
+    pub up: Vector3
+}
+
+impl CameraPath
+{
+    pub fn new(origin: Track<Vector3>, look_at: Track<Vector3>, fov: Track<f64>, up: Vector3) -> CameraPath
+    {
+        CameraPath { origin: origin, look_at: look_at, fov: fov, up: up }
+    }
+
+    pub fn perspective_at(&self, perspective: &Perspective, time: f64) -> Perspective
+    {
+        perspective.moved(self.origin.value_at(time), self.look_at.value_at(time), self.up, self.fov.value_at(time))
+    }
+}
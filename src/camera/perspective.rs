@@ -0,0 +1,86 @@
+use std::f64::consts::PI;
+use ray::Ray;
+use vector3::Vector3;
+use camera::{Camera, basis};
+use camera::aperture::Aperture;
+
+// A thin lens perspective camera, focused on a plane at focus_distance.
+#[derive(Clone)]
+pub struct Perspective
+{
+    lower_left: Vector3,
+    horizontal: Vector3,
+    vertical: Vector3,
+    pub origin: Vector3,
+    pub u: Vector3,
+    pub v: Vector3,
+    pub w: Vector3,
+    pub fov: f64,
+    pub focus_distance: f64,
+    lense_radius: f64,
+    pub aperture: Aperture,
+    // How far a barrel in front of the lens clips the aperture towards the corners of the image, where the aperture
+    // is seen at an angle and only the part inside both openings lets light through. At zero there's no clipping,
+    // and at one the clipped part reaches the middle of the aperture in the corners.
+    pub cat_eye: f64,
+    aspect: f64
+}
+
+impl Perspective
+{
+    pub fn new(origin: Vector3, look_at: Vector3, v_up: Vector3, fov: f64, aspect: f64, aperture: f64, focus_distance: f64) -> Perspective
+    {
+        let theta = fov * PI / 180.0;
+        let half_height = (theta / 2.0).tan();
+        let half_width = aspect * half_height;
+        let (u, v, w) = basis(origin, look_at, v_up);
+
+        Perspective
+        {
+            lower_left: origin - half_width * focus_distance * u - half_height * focus_distance * v - focus_distance * w,
+            horizontal: 2.0 * half_width * focus_distance * u,
+            vertical: 2.0 * half_height * focus_distance * v,
+            origin: origin,
+            u: u,
+            v: v,
+            w: w,
+            fov: fov,
+            focus_distance: focus_distance,
+            lense_radius: aperture / 2.0,
+            aperture: Aperture::Circle,
+            cat_eye: 0.0,
+            aspect: aspect
+        }
+    }
+
+    // The same camera with the same lens, moved and turned to look somewhere else, and focused on what it looks at.
+    pub fn moved(&self, origin: Vector3, look_at: Vector3, v_up: Vector3, fov: f64) -> Perspective
+    {
+        let mut moved = Perspective::new(origin, look_at, v_up, fov, self.aspect, 2.0 * self.lense_radius, (look_at - origin).length());
+        moved.aperture = self.aperture.clone();
+        moved.cat_eye = self.cat_eye;
+        moved
+    }
+}
+
+impl Camera for Perspective
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        let lens = self.aperture.sample();
+        if self.cat_eye > 0.0
+        {
+            // The clipping opening is offset towards the image's corners, in units of the aperture's radius.
+            let diagonal = (self.aspect * self.aspect + 1.0).sqrt();
+            let image = Vector3::new((2.0 * u - 1.0) * self.aspect, 2.0 * v - 1.0, 0.0) / diagonal;
+            if (lens - self.cat_eye * image).length_sqr() > 1.0
+            {
+                return None;
+            }
+        }
+
+        let rd = self.lense_radius * lens;
+        let offset = self.u * rd.x + self.v * rd.y;
+        Some(Ray{origin: self.origin + offset, direction: self.lower_left + (u * self.horizontal) + (v * self.vertical) - self.origin - offset, time: 0.0})
+    }
+}
@@ -0,0 +1,57 @@
+use vector3::Vector3;
+use camera::perspective::Perspective;
+
+// Camera settings the way a photographer gives them. Lengths on the camera are in millimeters, while the scene is
+// taken to be in meters, and the shutter time is in seconds.
+#[derive(Clone, Copy)]
+pub struct Physical
+{
+    pub focal_length: f64,
+    pub sensor_width: f64,
+    pub sensor_height: f64,
+    pub f_number: f64,
+    pub shutter: f64,
+    pub iso: f64
+}
+
+// Scene radiance of 1 is taken to be daylight, which the sunny 16 rule says is exposed right at f/16 with a shutter
+// time of one over the ISO.
+const SUNNY_16: f64 = 16.0 * 16.0;
+
+impl Physical
+{
+    // Defaults to a full frame sensor at ISO 100 and 1/100s, which at f/16 leaves scenes as bright as they'd be
+    // without an exposure.
+    pub fn new(focal_length: f64, f_number: f64) -> Physical
+    {
+        Physical { focal_length: focal_length, sensor_width: 36.0, sensor_height: 24.0, f_number: f_number, shutter: 1.0 / 100.0, iso: 100.0 }
+    }
+
+    // Vertical field of view in degrees, for the largest part of the sensor with the image's aspect ratio.
+    pub fn fov(&self, aspect: f64) -> f64
+    {
+        let height = if aspect >= self.sensor_width / self.sensor_height { self.sensor_width / aspect } else { self.sensor_height };
+        2.0 * (height / (2.0 * self.focal_length)).atan().to_degrees()
+    }
+
+    // Diameter of the entrance pupil in scene units.
+    pub fn aperture(&self) -> f64
+    {
+        self.focal_length / self.f_number / 1000.0
+    }
+
+    // How much light reaches the sensor compared to a scene exposed by the sunny 16 rule. Light gathered goes up
+    // with the shutter time and the area of the aperture, and the ISO scales how bright it comes out.
+    pub fn exposure(&self) -> f64
+    {
+        self.shutter * self.iso / (self.f_number * self.f_number) * SUNNY_16
+    }
+}
+
+impl Perspective
+{
+    pub fn physical(origin: Vector3, look_at: Vector3, v_up: Vector3, aspect: f64, physical: &Physical, focus_distance: f64) -> Perspective
+    {
+        Perspective::new(origin, look_at, v_up, physical.fov(aspect), aspect, physical.aperture(), focus_distance)
+    }
+}
@@ -0,0 +1,35 @@
+use random;
+use ray::Ray;
+use camera::Camera;
+
+// Keeps the shutter of another camera open from one time to another, casting each of its rays at a random time in
+// between so that anything moving meanwhile is blurred.
+pub struct Shutter
+{
+    pub camera: Box<Camera>,
+    pub open: f64,
+    pub close: f64
+}
+
+impl Shutter
+{
+    pub fn new(camera: Box<Camera>, open: f64, close: f64) -> Shutter
+    {
+        Shutter { camera: camera, open: open, close: close }
+    }
+}
+
+impl Camera for Shutter
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        self.get_weighted_ray(u, v).map(|(ray, _)| ray)
+    }
+
+    fn get_weighted_ray(&self, u: f64, v: f64) -> Option<(Ray, f64)>
+    {
+        let (ray, weight) = self.camera.get_weighted_ray(u, v)?;
+        let time = if self.close > self.open { self.open + (self.close - self.open) * random::next_f64() } else { self.open };
+        Some((Ray { origin: ray.origin, direction: ray.direction, time: time }, weight))
+    }
+}
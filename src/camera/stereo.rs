@@ -0,0 +1,109 @@
+use ray::Ray;
+use vector3::Vector3;
+use camera::{self, Camera, CameraOptions};
+use camera::ods::OmniStereo;
+use camera::perspective::Perspective;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Eye
+{
+    Left,
+    Right
+}
+
+// How the two views of a stereo pair are put into one image.
+#[derive(Clone, Copy, PartialEq)]
+pub enum StereoLayout
+{
+    SideBySide,
+    OverUnder,
+    // Red from the left eye and green and blue from the right, for red-cyan glasses.
+    Anaglyph
+}
+
+// One eye of a stereo rig, which sees what another camera does from a point offset to the side. Eyes are aimed so
+// that everything at the convergence distance along the view direction lines up in both views, appearing at the
+// depth of the screen, with nearer things in front of it and further ones behind.
+pub struct StereoEye
+{
+    pub camera: Box<Camera>,
+    pub offset: Vector3,
+    pub forward: Vector3,
+    pub convergence: f64
+}
+
+impl Camera for StereoEye
+{
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray>
+    {
+        self.get_weighted_ray(u, v).map(|(ray, _)| ray)
+    }
+
+    fn get_weighted_ray(&self, u: f64, v: f64) -> Option<(Ray, f64)>
+    {
+        let (ray, weight) = self.camera.get_weighted_ray(u, v)?;
+        let origin = ray.origin + self.offset;
+        let along = ray.direction.dot(self.forward);
+        if along <= 0.0
+        {
+            // Looking sideways or backwards, the ray never reaches the convergence plane, so keep it parallel.
+            return Some((Ray { origin: origin, direction: ray.direction, time: ray.time }, weight));
+        }
+
+        let target = ray.origin + ray.direction * (self.convergence / along);
+        Some((Ray { origin: origin, direction: target - origin, time: ray.time }, weight))
+    }
+}
+
+// Builds the left and right eye cameras of the given kind for a scene's perspective camera, interocular apart. The
+// omni-directional stereo camera ods is only available here, since it has no single eye version.
+pub fn eyes(name: &str, perspective: Perspective, options: &CameraOptions, aspect: f64, interocular: f64, convergence: f64) -> Option<(Box<Camera>, Box<Camera>)>
+{
+    let look_at = perspective.origin - perspective.w;
+    if name == "ods"
+    {
+        let eye = |e: Eye| Box::new(OmniStereo::new(perspective.origin, look_at, perspective.v, interocular, e)) as Box<Camera>;
+        return Some((eye(Eye::Left), eye(Eye::Right)));
+    }
+
+    let (right, forward) = (perspective.u, -perspective.w);
+    let left_camera = camera::from_name(name, perspective.clone(), options, aspect)?;
+    let right_camera = camera::from_name(name, perspective, options, aspect)?;
+    let eye = |camera: Box<Camera>, side: f64| Box::new(StereoEye
+    {
+        camera: camera,
+        offset: right * (side * interocular / 2.0),
+        forward: forward,
+        convergence: convergence
+    }) as Box<Camera>;
+    Some((eye(left_camera, -1.0), eye(right_camera, 1.0)))
+}
+
+// Puts the two views into one image, returning it with its width and height.
+pub fn compose(layout: StereoLayout, left: &[Vector3], right: &[Vector3], width: usize, height: usize) -> (Vec<Vector3>, usize, usize)
+{
+    match layout
+    {
+        StereoLayout::SideBySide =>
+        {
+            let mut colors = Vec::with_capacity(width * height * 2);
+            for y in 0..height
+            {
+                colors.extend_from_slice(&left[y * width..(y + 1) * width]);
+                colors.extend_from_slice(&right[y * width..(y + 1) * width]);
+            }
+            (colors, width * 2, height)
+        },
+        StereoLayout::OverUnder =>
+        {
+            let mut colors = left.to_vec();
+            colors.extend_from_slice(right);
+            (colors, width, height * 2)
+        },
+        StereoLayout::Anaglyph =>
+        {
+            let colors = left.iter().zip(right.iter()).map(|(l, r)| Vector3::new(l.x, r.y, r.z)).collect();
+            (colors, width, height)
+        }
+    }
+}
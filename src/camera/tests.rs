@@ -0,0 +1,302 @@
+use animation::Track;
+use random;
+use ray::Ray;
+use chi_square::{assert_fits, direction_bin, histogram};
+use camera::{Camera, CameraOptions, random_in_unit_disk};
+use camera::aperture::Aperture;
+use camera::equirectangular::Equirectangular;
+use camera::fisheye::{Fisheye, FisheyeMapping};
+use camera::lens_system::{self, LensSystem};
+use camera::orthographic::Orthographic;
+use camera::ods::OmniStereo;
+use camera::path::CameraPath;
+use camera::perspective::Perspective;
+use camera::physical::Physical;
+use camera::stereo::{self, Eye, StereoLayout};
+use vector3::{ZERO, Vector3};
+
+fn look_at() -> Vector3
+{
+    Vector3::new(0.0, 0.0, -1.0)
+}
+
+fn up() -> Vector3
+{
+    Vector3::new(0.0, 1.0, 0.0)
+}
+
+fn direction(camera: &Camera, u: f64, v: f64) -> Vector3
+{
+    camera.get_ray(u, v).unwrap().direction.normalized()
+}
+
+fn angle(a: Vector3, b: Vector3) -> f64
+{
+    a.normalized().dot(b.normalized()).clamp(-1.0, 1.0).acos()
+}
+
+#[test]
+fn random_in_unit_disk_is_uniform()
+{
+    random::seed(1);
+    let counts = histogram(32, 100000, ||
+    {
+        let p = random_in_unit_disk();
+        assert!(p.z == 0.0 && p.length_sqr() < 1.0);
+        let ring = (p.length_sqr() * 4.0) as usize;
+        direction_bin(p, Vector3::new(0.0, 0.0, 1.0), 1, 8, false).map(|bin| ring * 8 + bin)
+    });
+    assert_fits("random_in_unit_disk", &counts, &[1.0 / 32.0; 32]);
+}
+
+#[test]
+fn every_camera_looks_at_the_center_of_the_image()
+{
+    let cameras: Vec<Box<Camera>> = vec![
+        Box::new(Perspective::new(ZERO, look_at(), up(), 60.0, 2.0, 0.0, 1.0)),
+        Box::new(Orthographic::new(ZERO, look_at(), up(), 2.0, 2.0)),
+        Box::new(Fisheye::new(ZERO, look_at(), up(), 180.0, 2.0, FisheyeMapping::Equidistant)),
+        Box::new(Fisheye::new(ZERO, look_at(), up(), 180.0, 2.0, FisheyeMapping::Equisolid)),
+        Box::new(Equirectangular::new(ZERO, look_at(), up()))
+    ];
+
+    for camera in cameras.iter()
+    {
+        assert!(angle(direction(&**camera, 0.5, 0.5), look_at()) < 1e-9);
+        // Up and right in the image are up and right in the scene.
+        assert!(direction(&**camera, 0.5, 0.6).y > 0.0 || camera.get_ray(0.5, 0.6).unwrap().origin.y > 0.0);
+        assert!(direction(&**camera, 0.6, 0.5).x > 0.0 || camera.get_ray(0.6, 0.5).unwrap().origin.x > 0.0);
+    }
+}
+
+#[test]
+fn orthographic_rays_are_parallel_and_span_the_view()
+{
+    let camera = Orthographic::new(ZERO, look_at(), up(), 2.0, 1.5);
+    let bottom_left = camera.get_ray(0.0, 0.0).unwrap();
+    let top_right = camera.get_ray(1.0, 1.0).unwrap();
+    assert!((bottom_left.direction - top_right.direction).length() < 1e-12);
+    assert!((top_right.origin - bottom_left.origin - Vector3::new(3.0, 2.0, 0.0)).length() < 1e-12);
+}
+
+#[test]
+fn fisheye_spans_its_fov_across_the_image_circle()
+{
+    for &mapping in [FisheyeMapping::Equidistant, FisheyeMapping::Equisolid].iter()
+    {
+        let camera = Fisheye::new(ZERO, look_at(), up(), 220.0, 2.0, mapping);
+        assert!((angle(direction(&camera, 0.5, 1.0), look_at()).to_degrees() - 110.0).abs() < 1e-9);
+        assert!((angle(direction(&camera, 0.75, 0.5), look_at()).to_degrees() - 110.0).abs() < 1e-9);
+        assert!(camera.get_ray(0.76, 0.5).is_none());
+        assert!(camera.get_ray(0.0, 0.0).is_none());
+    }
+
+    // An equisolid lens magnifies the center, so halfway out the image circle it hasn't turned as far.
+    let equidistant = Fisheye::new(ZERO, look_at(), up(), 180.0, 1.0, FisheyeMapping::Equidistant);
+    let equisolid = Fisheye::new(ZERO, look_at(), up(), 180.0, 1.0, FisheyeMapping::Equisolid);
+    assert!((angle(direction(&equidistant, 0.75, 0.5), look_at()).to_degrees() - 45.0).abs() < 1e-9);
+    let expected = 2.0 * (0.5 * 45f64.to_radians().sin()).asin();
+    assert!((angle(direction(&equisolid, 0.75, 0.5), look_at()) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn equirectangular_covers_the_sphere()
+{
+    let camera = Equirectangular::new(ZERO, look_at(), up());
+    assert!((direction(&camera, 0.5, 1.0) - up()).length() < 1e-9);
+    assert!((direction(&camera, 0.5, 0.0) + up()).length() < 1e-9);
+    assert!((direction(&camera, 0.75, 0.5) - Vector3::new(1.0, 0.0, 0.0)).length() < 1e-9);
+    assert!((direction(&camera, 0.0, 0.5) - Vector3::new(0.0, 0.0, 1.0)).length() < 1e-9);
+    assert!((direction(&camera, 1.0, 0.5) - Vector3::new(0.0, 0.0, 1.0)).length() < 1e-9);
+}
+
+#[test]
+fn stereo_eyes_converge_at_the_convergence_distance()
+{
+    let perspective = Perspective::new(ZERO, look_at(), up(), 60.0, 1.0, 0.0, 1.0);
+    let (left, right) = stereo::eyes("perspective", perspective, &CameraOptions::new(), 1.0, 0.1, 4.0).unwrap();
+    for &(u, v) in [(0.5, 0.5), (0.1, 0.8), (0.9, 0.3)].iter()
+    {
+        let (l, r) = (left.get_ray(u, v).unwrap(), right.get_ray(u, v).unwrap());
+        assert!((r.origin - l.origin - Vector3::new(0.1, 0.0, 0.0)).length() < 1e-12);
+        let at_convergence = |ray: Ray| ray.translate_to(4.0 / -ray.direction.z);
+        assert!((at_convergence(l) - at_convergence(r)).length() < 1e-9);
+    }
+}
+
+#[test]
+fn ods_eyes_sit_on_the_interocular_circle()
+{
+    let left = OmniStereo::new(ZERO, look_at(), up(), 0.064, Eye::Left);
+    let right = OmniStereo::new(ZERO, look_at(), up(), 0.064, Eye::Right);
+    for &(u, v) in [(0.5, 0.5), (0.2, 0.7), (0.9, 0.1)].iter()
+    {
+        let (l, r) = (left.get_ray(u, v).unwrap(), right.get_ray(u, v).unwrap());
+        assert!((l.direction - r.direction).length() < 1e-12);
+        assert!(((r.origin - l.origin).length() - 0.064).abs() < 1e-12);
+        assert!((r.origin - l.origin).dot(l.direction).abs() < 1e-12);
+        // Looking along the ray, the right eye is on the right.
+        assert!((r.origin - l.origin).dot(l.direction.cross(up())) > 0.0);
+    }
+}
+
+#[test]
+fn stereo_layouts_arrange_both_views()
+{
+    let left = vec![Vector3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)];
+    let right = vec![Vector3::new(0.0, 3.0, 3.0), Vector3::new(0.0, 4.0, 4.0)];
+    let x = |colors: &[Vector3]| colors.iter().map(|c| c.x + c.y).collect::<Vec<f64>>();
+
+    let (colors, width, height) = stereo::compose(StereoLayout::SideBySide, &left, &right, 1, 2);
+    assert_eq!((width, height, x(&colors)), (2, 2, vec![1.0, 3.0, 2.0, 4.0]));
+    let (colors, width, height) = stereo::compose(StereoLayout::OverUnder, &left, &right, 1, 2);
+    assert_eq!((width, height, x(&colors)), (1, 4, vec![1.0, 2.0, 3.0, 4.0]));
+    let (colors, width, height) = stereo::compose(StereoLayout::Anaglyph, &left, &right, 1, 2);
+    assert_eq!((width, height), (1, 2));
+    assert!((colors[1] - Vector3::new(2.0, 4.0, 4.0)).length() < 1e-12);
+}
+
+#[test]
+fn physical_settings_give_the_expected_view_and_exposure()
+{
+    let physical = Physical::new(50.0, 16.0);
+    // A 50mm lens on a full frame sensor sees about 27 degrees vertically, or 40 across its width.
+    assert!((physical.fov(1.5) - 26.991).abs() < 1e-3);
+    assert!((physical.fov(3.0) - 2.0 * (6.0f64 / 50.0).atan().to_degrees()).abs() < 1e-9);
+    assert!((physical.aperture() - 0.003125).abs() < 1e-12);
+
+    // Sunny 16: f/16 at one over the ISO is the reference exposure, and a stop is a factor of two.
+    let mut sunny = physical;
+    sunny.shutter = 1.0 / 400.0;
+    sunny.iso = 400.0;
+    assert!((sunny.exposure() - 1.0).abs() < 1e-12);
+    sunny.shutter *= 2.0;
+    assert!((sunny.exposure() - 2.0).abs() < 1e-12);
+    sunny.f_number = 16.0 * 2f64.sqrt();
+    assert!((sunny.exposure() - 1.0).abs() < 1e-12);
+
+    let camera = Perspective::physical(ZERO, look_at(), up(), 1.5, &physical, 2.0);
+    // Rays leave from anywhere on the lens, but all pass through the same point on the plane in focus.
+    let ray = camera.get_ray(0.5, 1.0).unwrap();
+    let focused = ray.translate_to((-2.0 - ray.origin.z) / ray.direction.z);
+    assert!((angle(focused, look_at()).to_degrees() - 26.991 / 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn polygon_aperture_is_uniform_within_the_polygon()
+{
+    random::seed(2);
+    let blades = 5;
+    let rotation: f64 = 90.0;
+    let aperture = Aperture::Polygon { blades: blades, rotation: rotation };
+    // The apothem, how far the middle of each edge is from the center.
+    let apothem = (::std::f64::consts::PI / blades as f64).cos();
+    let counts = histogram(20, 100000, ||
+    {
+        let p = aperture.sample();
+        let angle = (p.y.atan2(p.x).to_degrees() - rotation).rem_euclid(360.0);
+        let sector = ((angle / (360.0 / blades as f64)) as usize).min(blades - 1);
+        let edge_angle = (rotation + (sector as f64 + 0.5) * 360.0 / blades as f64).to_radians();
+        let along = p.x * edge_angle.cos() + p.y * edge_angle.sin();
+        assert!(along <= apothem + 1e-12);
+        // Uniform in a triangle means the distance towards the edge has a density growing linearly.
+        let band = ((along / apothem).powi(2) * 4.0).min(3.0) as usize;
+        Some(sector * 4 + band)
+    });
+    assert_fits("Polygon aperture", &counts, &[1.0 / 20.0; 20]);
+}
+
+#[test]
+fn mask_aperture_only_samples_where_light_gets_through()
+{
+    random::seed(3);
+    // Two by two, with the top left pixel fully open and the bottom left one half open.
+    let aperture = Aperture::Mask { width: 2, height: 2, weights: vec![1.0, 0.0, 0.5, 0.0] };
+    let counts = histogram(2, 30000, ||
+    {
+        let p = aperture.sample();
+        assert!(p.x < 0.0 && p.x >= -1.0);
+        Some(if p.y > 0.0 { 0 } else { 1 })
+    });
+    assert_fits("Mask aperture", &counts, &[2.0 / 3.0, 1.0 / 3.0]);
+}
+
+#[test]
+fn cat_eye_vignetting_clips_the_aperture_towards_the_corners()
+{
+    random::seed(4);
+    let mut camera = Perspective::new(ZERO, look_at(), up(), 60.0, 1.5, 1.0, 1.0);
+    camera.cat_eye = 0.8;
+    let blocked = |u: f64, v: f64| (0..10000).filter(|_| camera.get_ray(u, v).is_none()).count();
+    assert_eq!(blocked(0.5, 0.5), 0);
+    let (edge, corner) = (blocked(1.0, 0.5), blocked(1.0, 1.0));
+    assert!(edge > 0 && corner > edge);
+
+    // In the corner, only the part of the lens inside both circles lets light through, which is about half of it.
+    let overlap = 2.0 * (0.4f64).acos() - 0.8 * (1.0 - 0.16f64).sqrt();
+    let expected = 1.0 - overlap / ::std::f64::consts::PI;
+    assert!((corner as f64 / 10000.0 - expected).abs() < 0.02);
+}
+
+#[test]
+fn lens_prescriptions_parse()
+{
+    let elements = lens_system::parse_prescription(lens_system::DOUBLE_GAUSS_50MM).unwrap();
+    assert_eq!(elements.len(), 11);
+    assert!(elements[5].radius == 0.0 && elements[5].ior == 0.0 && elements[5].aperture == 17.1);
+    assert!(lens_system::parse_prescription("# nothing but a comment").is_err());
+    assert!(lens_system::parse_prescription("29.475 3.76 1.67").is_err());
+    assert!(lens_system::parse_prescription("29.475 3.76 glass 25.2").is_err());
+}
+
+#[test]
+fn lens_system_focuses_by_moving_the_lens()
+{
+    let elements = lens_system::parse_prescription(lens_system::DOUBLE_GAUSS_50MM).unwrap();
+    let far = LensSystem::new(ZERO, look_at(), up(), elements.clone(), 36.0, 1.5, 10.0);
+    let near = LensSystem::new(ZERO, look_at(), up(), elements, 36.0, 1.5, 1.0);
+    let gap = |lens: &LensSystem| lens.elements.last().unwrap().thickness;
+    // Focused far away, the lens sits about its back focal length from the film, and focusing closer moves it out.
+    assert!(gap(&far) > 35.0 && gap(&far) < 45.0, "{}", gap(&far));
+    assert!(gap(&near) > gap(&far) + 1.0);
+
+    // Rays for the middle of the image, wherever they leave the lens, meet on the axis at the focus distance.
+    random::seed(1);
+    for &(ref lens, distance) in [(far, 10.0), (near, 1.0)].iter()
+    {
+        for _ in 0..100
+        {
+            let ray = match lens.get_ray(0.5, 0.5) { Some(ray) => ray, None => continue };
+            let focus = ray.translate_to((-distance - ray.origin.z) / ray.direction.z);
+            // What's left is the lens' spherical aberration, of a fraction of a milliradian.
+            assert!((focus.x * focus.x + focus.y * focus.y).sqrt() < distance * 1e-3);
+        }
+    }
+}
+
+#[test]
+fn lens_system_vignettes_the_corners()
+{
+    let elements = lens_system::parse_prescription(lens_system::DOUBLE_GAUSS_50MM).unwrap();
+    let lens = LensSystem::new(ZERO, look_at(), up(), elements, 36.0, 1.5, 10.0);
+    random::seed(1);
+    let brightness = |u: f64, v: f64| (0..10000).map(|_| lens.get_weighted_ray(u, v).map_or(0.0, |(_, w)| w)).sum::<f64>() / 10000.0;
+    let (center, halfway, corner) = (brightness(0.5, 0.5), brightness(0.75, 0.75), brightness(1.0, 1.0));
+    assert!((center - 1.0).abs() < 0.1, "{}", center);
+    assert!(halfway < center && corner < halfway / 2.0);
+}
+
+#[test]
+fn camera_paths_move_the_scene_camera()
+{
+    let mut perspective = Perspective::new(ZERO, look_at(), up(), 60.0, 1.5, 0.2, 1.0);
+    perspective.aperture = Aperture::Polygon { blades: 5, rotation: 0.0 };
+    let path = CameraPath::new(Track::linear(vec![(0.0, ZERO), (2.0, Vector3::new(4.0, 0.0, 0.0))]), Track::constant(Vector3::new(2.0, 0.0, -2.0)),
+        Track::linear(vec![(0.0, 60.0), (2.0, 30.0)]), up());
+
+    let moved = path.perspective_at(&perspective, 1.0);
+    assert!((moved.origin - Vector3::new(2.0, 0.0, 0.0)).length() < 1e-12);
+    assert!(angle(-moved.w, Vector3::new(0.0, 0.0, -1.0)) < 1e-12);
+    assert!(moved.fov == 45.0 && moved.focus_distance == 2.0);
+    assert!(matches!(moved.aperture, Aperture::Polygon { blades: 5, .. }));
+}
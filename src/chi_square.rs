@@ -0,0 +1,85 @@
+// Helpers shared by the statistical tests. Tests use a fixed seed, so a significance level this strict keeps them
+// from failing by chance while still catching a sampler that's off by more than a little.
+use std::f64::consts::PI;
+use vector3::Vector3;
+
+// Standard normal quantile for a significance level of 0.001.
+const Z_CRITICAL: f64 = 3.090;
+
+// Wilson-Hilferty approximation of the chi-square distribution's upper quantile.
+pub fn critical_value(degrees_of_freedom: usize) -> f64
+{
+    let k = degrees_of_freedom as f64;
+    let a = 2.0 / (9.0 * k);
+    k * (1.0 - a + Z_CRITICAL * a.sqrt()).powi(3)
+}
+
+pub fn assert_fits(name: &str, observed: &[usize], expected: &[f64])
+{
+    let total: usize = observed.iter().sum();
+    let mut statistic = 0.0;
+    for (o, e) in observed.iter().zip(expected.iter())
+    {
+        let e = e * total as f64;
+        assert!(e >= 5.0, "{}: too few samples per bin for a chi-square test", name);
+        statistic += (*o as f64 - e) * (*o as f64 - e) / e;
+    }
+
+    let limit = critical_value(observed.len() - 1);
+    assert!(statistic < limit, "{}: chi-square {:.2} exceeds {:.2} for {} bins", name, statistic, limit, observed.len());
+}
+
+// Two sample test for whether two histograms, built from the same number of samples, share a distribution.
+pub fn assert_same_distribution(name: &str, a: &[usize], b: &[usize])
+{
+    let mut statistic = 0.0;
+    let mut bins = 0;
+    for (x, y) in a.iter().zip(b.iter())
+    {
+        if x + y > 0
+        {
+            let difference = *x as f64 - *y as f64;
+            statistic += difference * difference / (x + y) as f64;
+            bins += 1;
+        }
+    }
+
+    let limit = critical_value(bins - 1);
+    assert!(statistic < limit, "{}: chi-square {:.2} exceeds {:.2} for {} bins", name, statistic, limit, bins);
+}
+
+// Bins a direction by the cosine of its angle from an axis and its rotation around it. Cosines are binned over
+// [-1, 1], or [0, 1] for a hemisphere, in which case directions below it land in no bin.
+pub fn direction_bin(direction: Vector3, axis: Vector3, cosine_bins: usize, phi_bins: usize, hemisphere: bool) -> Option<usize>
+{
+    let axis = axis.normalized();
+    let helper = if axis.x.abs() > 0.9 { Vector3::new(0.0, 1.0, 0.0) } else { Vector3::new(1.0, 0.0, 0.0) };
+    let tangent = helper.cross(axis).normalized();
+    let bitangent = axis.cross(tangent);
+
+    let direction = direction.normalized();
+    let cosine = direction.dot(axis);
+    let phi = direction.dot(bitangent).atan2(direction.dot(tangent)) + PI;
+    let low = if hemisphere { 0.0 } else { -1.0 };
+    if cosine < low
+    {
+        return None;
+    }
+
+    let i = (((cosine - low) / (1.0 - low)) * cosine_bins as f64).min(cosine_bins as f64 - 1.0) as usize;
+    let j = ((phi / (2.0 * PI)) * phi_bins as f64).min(phi_bins as f64 - 1.0) as usize;
+    Some(i * phi_bins + j)
+}
+
+pub fn histogram<F: FnMut() -> Option<usize>>(bins: usize, samples: usize, mut sample: F) -> Vec<usize>
+{
+    let mut counts = vec![0; bins];
+    for _ in 0..samples
+    {
+        if let Some(bin) = sample()
+        {
+            counts[bin] += 1;
+        }
+    }
+    counts
+}
@@ -0,0 +1,126 @@
+use aov::{self, Aovs};
+use vector3::{ONE, Vector3};
+
+const KERNEL: [f64; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+const ALBEDO_EPSILON: f64 = 0.01;
+
+// Edge-avoiding a-trous wavelet filter. Each pass widens a 5x5 B3 spline kernel by doubling the gap between taps,
+// and every tap is weighted down by how far its luminance (relative to the pixel's noise), normal, depth and
+// albedo are from the center pixel's. Filtering happens on the color divided by albedo, so surface texture
+// isn't blurred along with the noise.
+pub struct Denoiser
+{
+    pub iterations: usize,
+    pub strength: f64,
+    pub color_sigma: f64,
+    pub normal_power: f64,
+    pub depth_sigma: f64,
+    pub albedo_sigma: f64
+}
+
+impl Denoiser
+{
+    pub fn new(iterations: usize, strength: f64) -> Denoiser
+    {
+        Denoiser
+        {
+            iterations: iterations,
+            strength: strength,
+            color_sigma: 4.0,
+            normal_power: 64.0,
+            depth_sigma: 0.1,
+            albedo_sigma: 0.1
+        }
+    }
+
+    pub fn denoise(&self, color: &[Vector3], aovs: &Aovs) -> Vec<Vector3>
+    {
+        let (width, height) = (aovs.width, aovs.height);
+        let normal = aovs.normal();
+        let depth = aovs.depth();
+        let albedo: Vec<Vector3> = aovs.albedo().iter().map(|a| demodulation_albedo(*a)).collect();
+
+        let mut irradiance: Vec<Vector3> = color.iter().zip(albedo.iter()).map(|(c, a)| *c / *a).collect();
+        let mut variance: Vec<f64> = aovs.variance().iter().zip(albedo.iter()).map(|(v, a)|
+        {
+            let scale = aov::luminance(*a);
+            v / (scale * scale)
+        }).collect();
+
+        for iteration in 0..self.iterations
+        {
+            let step = 1 << iteration;
+            let mut next_irradiance = irradiance.clone();
+            let mut next_variance = variance.clone();
+
+            for y in 0..height
+            {
+                for x in 0..width
+                {
+                    let p = y * width + x;
+                    let luminance_p = aov::luminance(irradiance[p]);
+                    let color_scale = self.color_sigma * variance[p].sqrt() + 1e-4;
+                    let mut sum = Vector3::new(0.0, 0.0, 0.0);
+                    let mut sum_variance = 0.0;
+                    let mut sum_weight = 0.0;
+
+                    for (j, ky) in KERNEL.iter().enumerate()
+                    {
+                        let qy = y as isize + (j as isize - 2) * step;
+                        if qy < 0 || qy >= height as isize
+                        {
+                            continue;
+                        }
+
+                        for (i, kx) in KERNEL.iter().enumerate()
+                        {
+                            let qx = x as isize + (i as isize - 2) * step;
+                            if qx < 0 || qx >= width as isize
+                            {
+                                continue;
+                            }
+
+                            let q = qy as usize * width + qx as usize;
+                            let weight_color = (-(luminance_p - aov::luminance(irradiance[q])).abs() / color_scale).exp();
+                            let weight_normal = normal[p].dot(normal[q]).max(0.0).powf(self.normal_power);
+                            let weight_depth = (-(depth[p] - depth[q]).abs() / (self.depth_sigma * depth[p].max(depth[q]) + 1e-4)).exp();
+                            let weight_albedo = (-(albedo[p] - albedo[q]).length_sqr() / (self.albedo_sigma * self.albedo_sigma)).exp();
+
+                            // Pixels that hit nothing have no normal, and should still be filtered against each other.
+                            let weight_normal = if normal[p].length_sqr() == 0.0 && normal[q].length_sqr() == 0.0 { 1.0 } else { weight_normal };
+                            let weight = kx * ky * weight_color * weight_normal * weight_depth * weight_albedo;
+
+                            sum += weight * irradiance[q];
+                            sum_variance += weight * weight * variance[q];
+                            sum_weight += weight;
+                        }
+                    }
+
+                    if sum_weight > 0.0
+                    {
+                        next_irradiance[p] = sum / sum_weight;
+                        next_variance[p] = sum_variance / (sum_weight * sum_weight);
+                    }
+                }
+            }
+
+            irradiance = next_irradiance;
+            variance = next_variance;
+        }
+
+        color.iter().zip(irradiance.iter()).zip(albedo.iter()).map(|((raw, filtered), a)|
+        {
+            self.strength * (*filtered * *a) + (1.0 - self.strength) * *raw
+        }).collect()
+    }
+}
+
+// Pixels with a black albedo (or none, for misses) are left modulated, since dividing by them would blow up.
+fn demodulation_albedo(albedo: Vector3) -> Vector3
+{
+    if albedo.x < ALBEDO_EPSILON && albedo.y < ALBEDO_EPSILON && albedo.z < ALBEDO_EPSILON
+    {
+        return ONE;
+    }
+    Vector3::new(albedo.x.max(ALBEDO_EPSILON), albedo.y.max(ALBEDO_EPSILON), albedo.z.max(ALBEDO_EPSILON))
+}
@@ -0,0 +1,154 @@
+// Nodes that change the space a field is evaluated in, to move, repeat, twist or roughen it.
+use distance::DistanceField;
+use transform::Transform;
+use vector3::Vector3;
+
+// A field scaled, rotated and moved by a transform, with distances scaled along with it.
+pub struct Transformed
+{
+    pub field: Box<DistanceField>,
+    pub transform: Transform
+}
+
+impl Transformed
+{
+    pub fn new<F: DistanceField + 'static>(field: F, transform: Transform) -> Transformed
+    {
+        Transformed { field: Box::new(field), transform: transform }
+    }
+}
+
+impl DistanceField for Transformed
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.field.distance(self.transform.inverse_point(p)) * self.transform.scale
+    }
+
+    fn signed(&self) -> bool
+    {
+        self.field.signed()
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        self.field.coordinates(self.transform.inverse_point(p))
+    }
+}
+
+// A field repeated forever along each axis at the period given for it, or not along axes with a period of zero. The
+// field should fit within a period around the origin, or the copies will cut into each other.
+pub struct Repeat
+{
+    pub field: Box<DistanceField>,
+    pub period: Vector3
+}
+
+impl Repeat
+{
+    pub fn new<F: DistanceField + 'static>(field: F, period: Vector3) -> Repeat
+    {
+        Repeat { field: Box::new(field), period: period }
+    }
+
+    // Where the point falls in the copy around the origin.
+    fn wrap(&self, p: Vector3) -> Vector3
+    {
+        let wrap = |x: f64, period: f64| if period > 0.0 { x - period * (x / period).round() } else { x };
+        Vector3::new(wrap(p.x, self.period.x), wrap(p.y, self.period.y), wrap(p.z, self.period.z))
+    }
+}
+
+impl DistanceField for Repeat
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.field.distance(self.wrap(p))
+    }
+
+    fn signed(&self) -> bool
+    {
+        self.field.signed()
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        self.field.coordinates(self.wrap(p))
+    }
+}
+
+// A field twisted about the y axis, turning by the rate in degrees for each unit up. Twisting stretches space, so the
+// distances are only bounds when stepped through more carefully, by the step of the Sdf rendering it.
+pub struct Twist
+{
+    pub field: Box<DistanceField>,
+    pub rate: f64
+}
+
+impl Twist
+{
+    pub fn new<F: DistanceField + 'static>(field: F, rate: f64) -> Twist
+    {
+        Twist { field: Box::new(field), rate: rate }
+    }
+
+    fn untwist(&self, p: Vector3) -> Vector3
+    {
+        let (sin, cos) = (-self.rate * p.y).to_radians().sin_cos();
+        Vector3::new(p.x * cos - p.z * sin, p.y, p.x * sin + p.z * cos)
+    }
+}
+
+impl DistanceField for Twist
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.field.distance(self.untwist(p))
+    }
+
+    fn signed(&self) -> bool
+    {
+        self.field.signed()
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        self.field.coordinates(self.untwist(p))
+    }
+}
+
+// A field with ripples added to its surface, of the amplitude and with the given number of waves to each unit. Like
+// twisting, this needs a shorter step the steeper the ripples are.
+pub struct Displace
+{
+    pub field: Box<DistanceField>,
+    pub amplitude: f64,
+    pub frequency: f64
+}
+
+impl Displace
+{
+    pub fn new<F: DistanceField + 'static>(field: F, amplitude: f64, frequency: f64) -> Displace
+    {
+        Displace { field: Box::new(field), amplitude: amplitude, frequency: frequency }
+    }
+}
+
+impl DistanceField for Displace
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        let w = 2.0 * ::std::f64::consts::PI * self.frequency;
+        self.field.distance(p) + self.amplitude * (w * p.x).sin() * (w * p.y).sin() * (w * p.z).sin()
+    }
+
+    fn signed(&self) -> bool
+    {
+        self.field.signed()
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        self.field.coordinates(p)
+    }
+}
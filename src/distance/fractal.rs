@@ -0,0 +1,200 @@
+// Fractals, given by estimates of the distance to them worked out alongside the iterations that define them. The
+// estimates aren't signed, only falling towards zero near the set, and the coordinates they give are the orbit trap,
+// how near the orbit came to the origin, and how far through the iteration limit the point escaped.
+use distance::DistanceField;
+use vector3::Vector3;
+
+// The distance estimate, the orbit trap, and the fraction of the iteration limit used.
+type Orbit = (f64, f64, f64);
+
+// Points whose orbit stays within the bailout radius for every iteration count as part of the set.
+pub struct Mandelbulb
+{
+    pub power: f64,
+    pub iterations: u32,
+    pub bailout: f64
+}
+
+impl Mandelbulb
+{
+    pub fn new(power: f64, iterations: u32) -> Mandelbulb
+    {
+        Mandelbulb { power: power, iterations: iterations, bailout: 2.0 }
+    }
+
+    fn orbit(&self, p: Vector3) -> Orbit
+    {
+        // Raises z to the power in spherical coordinates and adds the starting point back on, keeping track of the
+        // derivative to estimate the distance from.
+        let (mut z, mut derivative, mut trap) = (p, 1.0, f64::MAX);
+        let mut radius = z.length();
+        let mut i = 0;
+        while i < self.iterations && radius <= self.bailout
+        {
+            trap = trap.min(radius);
+            let theta = if radius > 0.0 { (z.z / radius).acos() } else { 0.0 } * self.power;
+            let phi = z.y.atan2(z.x) * self.power;
+            derivative = radius.powf(self.power - 1.0) * self.power * derivative + 1.0;
+            z = Vector3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()) * radius.powf(self.power) + p;
+            radius = z.length();
+            i += 1;
+        }
+        (escape_distance(radius, derivative, self.power, i), trap.min(1.0), i as f64 / self.iterations as f64)
+    }
+}
+
+impl DistanceField for Mandelbulb
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.orbit(p).0
+    }
+
+    fn signed(&self) -> bool
+    {
+        false
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        let (_, trap, escaped) = self.orbit(p);
+        (trap, escaped)
+    }
+}
+
+// Folds space back into a box and turns it inside out through a sphere, then scales it up. Scales around two give
+// a box of side 12 centered on the origin, and negative scales between -1 and -2 give rounder shapes inside it.
+pub struct Mandelbox
+{
+    pub scale: f64,
+    pub iterations: u32,
+    // Inside the minimum radius, the sphere fold scales up by a fixed amount rather than inverting.
+    pub min_radius: f64,
+    pub fixed_radius: f64,
+    pub bailout: f64
+}
+
+impl Mandelbox
+{
+    pub fn new(scale: f64, iterations: u32) -> Mandelbox
+    {
+        Mandelbox { scale: scale, iterations: iterations, min_radius: 0.5, fixed_radius: 1.0, bailout: 100.0 }
+    }
+
+    fn orbit(&self, p: Vector3) -> Orbit
+    {
+        let (min_sqr, fixed_sqr) = (self.min_radius * self.min_radius, self.fixed_radius * self.fixed_radius);
+        let fold = |x: f64| x.clamp(-1.0, 1.0) * 2.0 - x;
+        let (mut z, mut derivative, mut trap) = (p, 1.0, f64::MAX);
+        let mut i = 0;
+        while i < self.iterations && z.length() <= self.bailout
+        {
+            z = Vector3::new(fold(z.x), fold(z.y), fold(z.z));
+            let radius_sqr = z.length_sqr();
+            trap = trap.min(radius_sqr.sqrt() / self.fixed_radius);
+            let factor = if radius_sqr < min_sqr { fixed_sqr / min_sqr } else if radius_sqr < fixed_sqr { fixed_sqr / radius_sqr } else { 1.0 };
+            z = z * factor * self.scale + p;
+            derivative = derivative * factor * self.scale.abs() + 1.0;
+            i += 1;
+        }
+        (z.length() / derivative, trap.min(1.0), i as f64 / self.iterations as f64)
+    }
+}
+
+impl DistanceField for Mandelbox
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.orbit(p).0
+    }
+
+    fn signed(&self) -> bool
+    {
+        false
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        let (_, trap, escaped) = self.orbit(p);
+        (trap, escaped)
+    }
+}
+
+// The Julia set of z² + c over quaternions, cut through where the fourth component is zero.
+pub struct QuaternionJulia
+{
+    pub c: [f64; 4],
+    pub iterations: u32,
+    pub bailout: f64
+}
+
+impl QuaternionJulia
+{
+    pub fn new(c: [f64; 4], iterations: u32) -> QuaternionJulia
+    {
+        QuaternionJulia { c: c, iterations: iterations, bailout: 4.0 }
+    }
+
+    fn orbit(&self, p: Vector3) -> Orbit
+    {
+        let (mut z, mut derivative, mut trap) = ([p.x, p.y, p.z, 0.0], [1.0, 0.0, 0.0, 0.0], f64::MAX);
+        let mut i = 0;
+        while i < self.iterations && norm(z) <= self.bailout
+        {
+            trap = trap.min(norm(z));
+            let twice = multiply(z, derivative);
+            derivative = [2.0 * twice[0], 2.0 * twice[1], 2.0 * twice[2], 2.0 * twice[3]];
+            let square = multiply(z, z);
+            z = [square[0] + self.c[0], square[1] + self.c[1], square[2] + self.c[2], square[3] + self.c[3]];
+            i += 1;
+        }
+        let radius = norm(z);
+        (escape_distance(radius, norm(derivative), 2.0, i), trap.min(1.0), i as f64 / self.iterations as f64)
+    }
+}
+
+impl DistanceField for QuaternionJulia
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        self.orbit(p).0
+    }
+
+    fn signed(&self) -> bool
+    {
+        false
+    }
+
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        let (_, trap, escaped) = self.orbit(p);
+        (trap, escaped)
+    }
+}
+
+// The distance to a set of points whose orbits under z^power + c stay bounded, from where the orbit of a point outside
+// it had got to after some iterations and how fast that was changing with the point. This is the lower bound on the
+// distance from the potential G = ln|z| / power^iterations, sinh G / (2 e^G |G'|), which is the usual estimate of
+// |z| ln|z| / 2|z'| close to the set, but unlike it is still safe to step by far away. Points that never escaped are
+// counted as in the set.
+fn escape_distance(radius: f64, derivative: f64, power: f64, iterations: u32) -> f64
+{
+    let growth = power.powi(iterations as i32);
+    let potential = radius.ln() / growth;
+    ((1.0 - (-2.0 * potential).exp()) * radius * growth / (4.0 * derivative)).max(0.0)
+}
+
+fn multiply(a: [f64; 4], b: [f64; 4]) -> [f64; 4]
+{
+    [
+        a[0] * b[0] - a[1] * b[1] - a[2] * b[2] - a[3] * b[3],
+        a[0] * b[1] + a[1] * b[0] + a[2] * b[3] - a[3] * b[2],
+        a[0] * b[2] - a[1] * b[3] + a[2] * b[0] + a[3] * b[1],
+        a[0] * b[3] + a[1] * b[2] - a[2] * b[1] + a[3] * b[0]
+    ]
+}
+
+fn norm(q: [f64; 4]) -> f64
+{
+    (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt()
+}
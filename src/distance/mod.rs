@@ -0,0 +1,88 @@
+// Signed distance fields, which give how far any point is from the nearest surface, negative inside. Shapes are built
+// from primitives, combined and bent with the nodes here, then rendered by sphere tracing with renderable::sdf::Sdf.
+pub mod shapes;
+pub mod domain;
+pub mod fractal;
+#[cfg(test)]
+mod tests;
+
+use renderable::csg::Operation;
+use vector3::Vector3;
+
+pub trait DistanceField: Sync
+{
+    fn distance(&self, p: Vector3) -> f64;
+    // Whether the distance is negative inside, so that crossings can be found exactly where its sign changes.
+    // Estimates of the distance to fractals only ever approach zero.
+    fn signed(&self) -> bool
+    {
+        true
+    }
+    // Surface coordinates at a point, which fractals fill in with orbit traps.
+    #[allow(unused_variables)]
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        (0.0, 0.0)
+    }
+}
+
+// How fast the distance changes, found by central differences, which points out of the surface.
+pub fn gradient(field: &DistanceField, p: Vector3, step: f64) -> Vector3
+{
+    let axis = |v: Vector3| (field.distance(p + v * step) - field.distance(p - v * step)) / (2.0 * step);
+    Vector3::new(axis(Vector3::new(1.0, 0.0, 0.0)), axis(Vector3::new(0.0, 1.0, 0.0)), axis(Vector3::new(0.0, 0.0, 1.0)))
+}
+
+// Two fields combined like CSG, with the seam between them rounded off over the radius. A radius of zero gives sharp
+// seams, and the distances are then exact outside the combination.
+pub struct Smooth
+{
+    pub operation: Operation,
+    pub a: Box<DistanceField>,
+    pub b: Box<DistanceField>,
+    pub radius: f64
+}
+
+impl Smooth
+{
+    pub fn new<A: DistanceField + 'static, B: DistanceField + 'static>(operation: Operation, a: A, b: B, radius: f64) -> Smooth
+    {
+        Smooth { operation: operation, a: Box::new(a), b: Box::new(b), radius: radius }
+    }
+}
+
+// The smaller of two values, blended with the larger where they're within the radius of each other.
+fn smooth_min(a: f64, b: f64, radius: f64) -> f64
+{
+    if radius <= 0.0
+    {
+        return a.min(b);
+    }
+    let h = (radius - (a - b).abs()).max(0.0) / radius;
+    a.min(b) - h * h * radius / 4.0
+}
+
+impl DistanceField for Smooth
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        let (a, b) = (self.a.distance(p), self.b.distance(p));
+        match self.operation
+        {
+            Operation::Union => smooth_min(a, b, self.radius),
+            Operation::Intersection => -smooth_min(-a, -b, self.radius),
+            Operation::Difference => -smooth_min(-a, b, self.radius)
+        }
+    }
+
+    fn signed(&self) -> bool
+    {
+        self.a.signed() && self.b.signed()
+    }
+
+    // Taken from whichever field is nearer.
+    fn coordinates(&self, p: Vector3) -> (f64, f64)
+    {
+        if self.a.distance(p).abs() <= self.b.distance(p).abs() { self.a.coordinates(p) } else { self.b.coordinates(p) }
+    }
+}
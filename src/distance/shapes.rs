@@ -0,0 +1,109 @@
+use distance::DistanceField;
+use vector3::Vector3;
+
+pub struct Sphere
+{
+    pub center: Vector3,
+    pub radius: f64
+}
+
+impl Sphere
+{
+    pub fn new(center: Vector3, radius: f64) -> Sphere
+    {
+        Sphere { center: center, radius: radius }
+    }
+}
+
+impl DistanceField for Sphere
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        (p - self.center).length() - self.radius
+    }
+}
+
+// An axis aligned box, with its edges and corners rounded off over the rounding radius while keeping its size.
+pub struct Cuboid
+{
+    pub center: Vector3,
+    pub size: Vector3,
+    pub rounding: f64
+}
+
+impl Cuboid
+{
+    pub fn new(center: Vector3, size: Vector3) -> Cuboid
+    {
+        Cuboid::rounded(center, size, 0.0)
+    }
+
+    pub fn rounded(center: Vector3, size: Vector3, rounding: f64) -> Cuboid
+    {
+        Cuboid { center: center, size: size, rounding: rounding }
+    }
+}
+
+impl DistanceField for Cuboid
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        let d = p - self.center;
+        let inset = self.size / 2.0 - Vector3::new(self.rounding, self.rounding, self.rounding);
+        let outside = Vector3::new(d.x.abs() - inset.x, d.y.abs() - inset.y, d.z.abs() - inset.z);
+        let clamped = Vector3::new(outside.x.max(0.0), outside.y.max(0.0), outside.z.max(0.0));
+        clamped.length() + outside.x.max(outside.y).max(outside.z).min(0.0) - self.rounding
+    }
+}
+
+// Every point within the radius of the line segment between two points.
+pub struct Capsule
+{
+    pub a: Vector3,
+    pub b: Vector3,
+    pub radius: f64
+}
+
+impl Capsule
+{
+    pub fn new(a: Vector3, b: Vector3, radius: f64) -> Capsule
+    {
+        Capsule { a: a, b: b, radius: radius }
+    }
+}
+
+impl DistanceField for Capsule
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        let (pa, ba) = (p - self.a, self.b - self.a);
+        let f = (pa.dot(ba) / ba.dot(ba)).clamp(0.0, 1.0);
+        (pa - ba * f).length() - self.radius
+    }
+}
+
+// A torus lying flat around the y axis through its center.
+pub struct Torus
+{
+    pub center: Vector3,
+    pub major_radius: f64,
+    pub minor_radius: f64
+}
+
+impl Torus
+{
+    pub fn new(center: Vector3, major_radius: f64, minor_radius: f64) -> Torus
+    {
+        Torus { center: center, major_radius: major_radius, minor_radius: minor_radius }
+    }
+}
+
+impl DistanceField for Torus
+{
+    fn distance(&self, p: Vector3) -> f64
+    {
+        let d = p - self.center;
+        let ring = (d.x * d.x + d.z * d.z).sqrt() - self.major_radius;
+        (ring * ring + d.y * d.y).sqrt() - self.minor_radius
+    }
+}
@@ -0,0 +1,108 @@
+use distance::{DistanceField, Smooth, gradient};
+use distance::domain::{Displace, Repeat, Transformed, Twist};
+use distance::fractal::{Mandelbox, Mandelbulb, QuaternionJulia};
+use distance::shapes::{Capsule, Cuboid, Sphere, Torus};
+use renderable::csg::Operation;
+use transform::Transform;
+use vector3::{ZERO, Vector3};
+
+#[test]
+fn primitives_are_zero_on_their_surfaces()
+{
+    let x = Vector3::new(1.0, 0.0, 0.0);
+    assert!((Sphere::new(x, 2.0).distance(Vector3::new(1.0, 3.0, 0.0)) - 1.0).abs() < 1e-12);
+    let cuboid = Cuboid::new(ZERO, Vector3::new(2.0, 4.0, 6.0));
+    assert!(cuboid.distance(Vector3::new(1.0, 0.5, 0.5)).abs() < 1e-12);
+    assert!((cuboid.distance(ZERO) + 1.0).abs() < 1e-12);
+    // Rounding leaves the faces where they were, but pulls the corners in.
+    let rounded = Cuboid::rounded(ZERO, Vector3::new(2.0, 2.0, 2.0), 0.5);
+    assert!(rounded.distance(x).abs() < 1e-12);
+    assert!(rounded.distance(Vector3::new(1.0, 1.0, 1.0)) > 0.3);
+    let capsule = Capsule::new(ZERO, x * 3.0, 0.5);
+    assert!(capsule.distance(Vector3::new(1.5, 0.5, 0.0)).abs() < 1e-12 && capsule.distance(Vector3::new(-0.5, 0.0, 0.0)).abs() < 1e-12);
+    let torus = Torus::new(ZERO, 2.0, 0.5);
+    assert!(torus.distance(Vector3::new(0.0, 0.5, 2.0)).abs() < 1e-12 && (torus.distance(ZERO) - 1.5).abs() < 1e-12);
+}
+
+#[test]
+fn distances_are_exact_for_primitives()
+{
+    // Exact distance fields change by exactly one for each unit moved away from the surface.
+    let fields: Vec<Box<DistanceField>> = vec![
+        Box::new(Sphere::new(ZERO, 1.0)),
+        Box::new(Cuboid::rounded(ZERO, Vector3::new(1.0, 2.0, 3.0), 0.2)),
+        Box::new(Capsule::new(ZERO, Vector3::new(1.0, 1.0, 0.0), 0.3)),
+        Box::new(Torus::new(ZERO, 1.0, 0.25)),
+        Box::new(Transformed::new(Cuboid::new(ZERO, Vector3::new(1.0, 1.0, 1.0)), Transform::new(ZERO, Vector3::new(30.0, 20.0, 10.0), 2.0)))
+    ];
+    for field in fields.iter()
+    {
+        for &p in [Vector3::new(2.0, 1.5, -1.0), Vector3::new(-0.3, 3.0, 0.7), Vector3::new(1.7, -0.2, 2.5)].iter()
+        {
+            assert!((gradient(&**field, p, 1e-6).length() - 1.0).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn smooth_combinations_round_off_seams()
+{
+    let (a, b) = (Sphere::new(Vector3::new(-0.8, 0.0, 0.0), 1.0), Sphere::new(Vector3::new(0.8, 0.0, 0.0), 1.0));
+    let seam = Vector3::new(0.0, 0.6, 0.0);
+    let sharp = Smooth::new(Operation::Union, Sphere::new(a.center, 1.0), Sphere::new(b.center, 1.0), 0.0);
+    let smooth = Smooth::new(Operation::Union, a, b, 0.5);
+    assert!((sharp.distance(seam) - ((0.8f64 * 0.8 + 0.36).sqrt() - 1.0)).abs() < 1e-12);
+    // Smoothing fills in the crease, and leaves points well away from it alone.
+    assert!(smooth.distance(seam) < sharp.distance(seam));
+    assert_eq!(smooth.distance(Vector3::new(-2.5, 0.0, 0.0)), sharp.distance(Vector3::new(-2.5, 0.0, 0.0)));
+
+    let difference = Smooth::new(Operation::Difference, Sphere::new(ZERO, 1.0), Sphere::new(Vector3::new(1.0, 0.0, 0.0), 0.5), 0.0);
+    assert!(difference.distance(Vector3::new(0.9, 0.0, 0.0)) > 0.0 && difference.distance(Vector3::new(-0.9, 0.0, 0.0)) < 0.0);
+    let intersection = Smooth::new(Operation::Intersection, Sphere::new(ZERO, 1.0), Sphere::new(Vector3::new(1.0, 0.0, 0.0), 1.0), 0.0);
+    assert!(intersection.distance(Vector3::new(0.5, 0.0, 0.0)) < 0.0 && intersection.distance(Vector3::new(-0.5, 0.0, 0.0)) > 0.0);
+}
+
+#[test]
+fn domain_nodes_move_space_around()
+{
+    let repeated = Repeat::new(Sphere::new(ZERO, 0.5), Vector3::new(2.0, 0.0, 0.0));
+    assert!(repeated.distance(Vector3::new(10.0, 0.0, 0.0)) < -0.49);
+    assert!((repeated.distance(Vector3::new(0.0, 3.0, 0.0)) - 2.5).abs() < 1e-12);
+
+    // A bar along x, twisted a quarter turn by y = 1, lies along z there instead.
+    let bar = Twist::new(Cuboid::new(ZERO, Vector3::new(4.0, 10.0, 0.2)), 90.0);
+    assert!(bar.distance(Vector3::new(1.5, 0.0, 0.0)) < 0.0);
+    assert!(bar.distance(Vector3::new(0.0, 1.0, 1.5)) < 0.0 && bar.distance(Vector3::new(1.5, 1.0, 0.0)) > 0.0);
+
+    let rippled = Displace::new(Sphere::new(ZERO, 1.0), 0.1, 1.0);
+    assert!((rippled.distance(Vector3::new(0.0, 0.0, 1.0)) - 0.0).abs() < 1e-12);
+    let p = Vector3::new(0.25, 0.25, 0.25) * (1.0 / 0.75f64.sqrt());
+    assert!((rippled.distance(p) - (p.length() - 1.0 + 0.1 * (0.5 * ::std::f64::consts::PI / 0.75f64.sqrt()).sin().powi(3))).abs() < 1e-12);
+}
+
+#[test]
+fn fractal_estimates_never_overshoot()
+{
+    // The origin is in each of these sets, so nothing is further from them than from the origin.
+    let fractals: Vec<Box<DistanceField>> = vec![
+        Box::new(Mandelbulb::new(8.0, 12)),
+        Box::new(Mandelbox::new(2.0, 12)),
+        Box::new(QuaternionJulia::new([-0.2, 0.6, 0.2, 0.2], 12))
+    ];
+    for fractal in fractals.iter()
+    {
+        assert!(!fractal.signed());
+        let (trap, escaped) = fractal.coordinates(ZERO);
+        assert!(fractal.distance(ZERO) <= 0.0 && trap <= 1.0 && escaped == 1.0);
+        for &p in [Vector3::new(7.0, 0.5, -1.0), Vector3::new(-0.3, 9.0, 0.7), Vector3::new(20.0, -20.0, 20.0)].iter()
+        {
+            let (trap, escaped) = fractal.coordinates(p);
+            assert!(fractal.distance(p) > 0.0 && fractal.distance(p) <= p.length());
+            assert!((0.0..=1.0).contains(&trap) && (0.0..1.0).contains(&escaped));
+        }
+    }
+
+    // Transforming fractals moves their coordinates along with them.
+    let moved = Transformed::new(Mandelbulb::new(8.0, 12), Transform::new(Vector3::new(5.0, 0.0, 0.0), ZERO, 2.0));
+    assert!(!moved.signed() && moved.coordinates(Vector3::new(7.0, 1.0, 0.0)) == Mandelbulb::new(8.0, 12).coordinates(Vector3::new(1.0, 0.5, 0.0)));
+}
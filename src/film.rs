@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use rgb::RGBA;
+use filter::Filter;
+use vector3::{ZERO, Vector3};
+
+#[derive(Clone, Copy)]
+pub struct Pixel
+{
+    pub color: Vector3,
+    pub weight: f64
+}
+
+const EMPTY_PIXEL: Pixel = Pixel { color: ZERO, weight: 0.0 };
+
+pub struct Film
+{
+    pub width: usize,
+    pub height: usize,
+    filter: Arc<Filter>,
+    pixels: Vec<Pixel>
+}
+
+// A rectangle of film that samples within [x0, x1) x [y0, y1) can splat into, padded by the filter radius.
+// Tiles rendered in parallel overlap along their padding, and their sums are added back into the film on merge.
+pub struct FilmTile
+{
+    filter: Arc<Filter>,
+    x0: usize,
+    y0: usize,
+    width: usize,
+    height: usize,
+    pixels: Vec<Pixel>
+}
+
+impl Film
+{
+    pub fn new(width: usize, height: usize, filter: Box<Filter>) -> Film
+    {
+        Film { width: width, height: height, filter: Arc::from(filter), pixels: vec![EMPTY_PIXEL; width * height] }
+    }
+
+    pub fn tile(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> FilmTile
+    {
+        let radius = self.filter.radius();
+        let px0 = ((x0 as f64) - 0.5 - radius).floor().max(0.0) as usize;
+        let py0 = ((y0 as f64) - 0.5 - radius).floor().max(0.0) as usize;
+        let px1 = (((x1 as f64) - 0.5 + radius).floor().max(0.0) as usize + 1).min(self.width);
+        let py1 = (((y1 as f64) - 0.5 + radius).floor().max(0.0) as usize + 1).min(self.height);
+
+        FilmTile
+        {
+            filter: self.filter.clone(),
+            x0: px0,
+            y0: py0,
+            width: px1 - px0,
+            height: py1 - py0,
+            pixels: vec![EMPTY_PIXEL; (px1 - px0) * (py1 - py0)]
+        }
+    }
+
+    pub fn merge(&mut self, tile: FilmTile)
+    {
+        for y in 0..tile.height
+        {
+            for x in 0..tile.width
+            {
+                let source = tile.pixels[y * tile.width + x];
+                let pixel = &mut self.pixels[(tile.y0 + y) * self.width + tile.x0 + x];
+                pixel.color += source.color;
+                pixel.weight += source.weight;
+            }
+        }
+    }
+
+    pub fn get_color(&self, x: usize, y: usize) -> Vector3
+    {
+        let pixel = self.pixels[y * self.width + x];
+        if pixel.weight > 0.0 { pixel.color / pixel.weight } else { ZERO }
+    }
+
+    pub fn colors(&self) -> Vec<Vector3>
+    {
+        (0..self.width * self.height).map(|i| self.get_color(i % self.width, i / self.width)).collect()
+    }
+}
+
+impl FilmTile
+{
+    pub fn add_sample(&mut self, x: f64, y: f64, color: Vector3)
+    {
+        splat(&*self.filter, &mut self.pixels, self.x0, self.y0, self.width, self.height, x, y, color);
+    }
+}
+
+// Adds a sample at raster position (x, y) to every pixel whose center lies within the filter radius.
+#[allow(clippy::too_many_arguments)]
+fn splat(filter: &Filter, pixels: &mut [Pixel], x0: usize, y0: usize, width: usize, height: usize, x: f64, y: f64, color: Vector3)
+{
+    let radius = filter.radius();
+    let min_x = ((x - 0.5 - radius).ceil().max(x0 as f64)) as usize;
+    let min_y = ((y - 0.5 - radius).ceil().max(y0 as f64)) as usize;
+    let max_x = (x - 0.5 + radius).floor().min((x0 + width) as f64 - 1.0);
+    let max_y = (y - 0.5 + radius).floor().min((y0 + height) as f64 - 1.0);
+    if max_x < min_x as f64 || max_y < min_y as f64
+    {
+        return;
+    }
+
+    for py in min_y..(max_y as usize + 1)
+    {
+        for px in min_x..(max_x as usize + 1)
+        {
+            let weight = filter.evaluate((px as f64) + 0.5 - x, (py as f64) + 0.5 - y);
+            if weight != 0.0
+            {
+                let pixel = &mut pixels[(py - y0) * width + px - x0];
+                pixel.color += weight * color;
+                pixel.weight += weight;
+            }
+        }
+    }
+}
+
+pub fn to_rgba(colors: &[Vector3]) -> Vec<RGBA<u8>>
+{
+    colors.iter().map(|color| RGBA
+    {
+        r: to_byte(color.x),
+        g: to_byte(color.y),
+        b: to_byte(color.z),
+        a: 255
+    }).collect()
+}
+
+fn to_byte(value: f64) -> u8
+{
+    (value.max(0.0).sqrt() * 255.0).min(255.0) as u8
+}
@@ -0,0 +1,27 @@
+use filter::Filter;
+
+pub struct BoxFilter
+{
+    pub radius: f64
+}
+
+impl BoxFilter
+{
+    pub fn new(radius: f64) -> BoxFilter
+    {
+        BoxFilter { radius: radius }
+    }
+}
+
+impl Filter for BoxFilter
+{
+    fn radius(&self) -> f64
+    {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64
+    {
+        if x.abs() <= self.radius && y.abs() <= self.radius { 1.0 } else { 0.0 }
+    }
+}
@@ -0,0 +1,35 @@
+use filter::Filter;
+
+pub struct GaussianFilter
+{
+    pub radius: f64,
+    pub alpha: f64,
+    edge: f64
+}
+
+impl GaussianFilter
+{
+    pub fn new(radius: f64, alpha: f64) -> GaussianFilter
+    {
+        GaussianFilter { radius: radius, alpha: alpha, edge: (-alpha * radius * radius).exp() }
+    }
+
+    // Shifted down by the value at the radius so the filter falls to zero at its edge instead of being cut off.
+    fn gaussian(&self, x: f64) -> f64
+    {
+        ((-self.alpha * x * x).exp() - self.edge).max(0.0)
+    }
+}
+
+impl Filter for GaussianFilter
+{
+    fn radius(&self) -> f64
+    {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64
+    {
+        self.gaussian(x) * self.gaussian(y)
+    }
+}
@@ -0,0 +1,48 @@
+use std::f64::consts::PI;
+use filter::Filter;
+
+pub struct LanczosFilter
+{
+    pub radius: f64,
+    pub tau: f64
+}
+
+impl LanczosFilter
+{
+    pub fn new(radius: f64, tau: f64) -> LanczosFilter
+    {
+        LanczosFilter { radius: radius, tau: tau }
+    }
+
+    fn sinc(x: f64) -> f64
+    {
+        if x.abs() < 1e-5
+        {
+            return 1.0;
+        }
+        (PI * x).sin() / (PI * x)
+    }
+
+    // Sinc windowed by a wider sinc, with tau controlling the width of the window.
+    fn lanczos(&self, x: f64) -> f64
+    {
+        if x.abs() > self.radius
+        {
+            return 0.0;
+        }
+        LanczosFilter::sinc(x) * LanczosFilter::sinc(x / self.tau)
+    }
+}
+
+impl Filter for LanczosFilter
+{
+    fn radius(&self) -> f64
+    {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64
+    {
+        self.lanczos(x) * self.lanczos(y)
+    }
+}
@@ -0,0 +1,52 @@
+use filter::Filter;
+
+pub struct MitchellFilter
+{
+    pub radius: f64,
+    pub b: f64,
+    pub c: f64
+}
+
+impl MitchellFilter
+{
+    pub fn new(radius: f64, b: f64, c: f64) -> MitchellFilter
+    {
+        MitchellFilter { radius: radius, b: b, c: c }
+    }
+
+    // The Mitchell-Netravali cubic is defined over [-2, 2], so x is first remapped from [-radius, radius].
+    fn mitchell(&self, x: f64) -> f64
+    {
+        let x = (2.0 * x / self.radius).abs();
+        let b = self.b;
+        let c = self.c;
+
+        if x > 2.0
+        {
+            0.0
+        }
+        else if x > 1.0
+        {
+            ((-b - 6.0 * c) * x * x * x + (6.0 * b + 30.0 * c) * x * x +
+                (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)) / 6.0
+        }
+        else
+        {
+            ((12.0 - 9.0 * b - 6.0 * c) * x * x * x + (-18.0 + 12.0 * b + 6.0 * c) * x * x +
+                (6.0 - 2.0 * b)) / 6.0
+        }
+    }
+}
+
+impl Filter for MitchellFilter
+{
+    fn radius(&self) -> f64
+    {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64
+    {
+        self.mitchell(x) * self.mitchell(y)
+    }
+}
@@ -0,0 +1,30 @@
+pub mod box_filter;
+pub mod tent;
+pub mod gaussian;
+pub mod mitchell;
+pub mod lanczos;
+
+use filter::box_filter::BoxFilter;
+use filter::tent::TentFilter;
+use filter::gaussian::GaussianFilter;
+use filter::mitchell::MitchellFilter;
+use filter::lanczos::LanczosFilter;
+
+pub trait Filter: Sync + Send
+{
+    fn radius(&self) -> f64;
+    fn evaluate(&self, x: f64, y: f64) -> f64;
+}
+
+pub fn from_name(name: &str, radius: Option<f64>) -> Option<Box<Filter>>
+{
+    match name
+    {
+        "box" => Some(Box::new(BoxFilter::new(radius.unwrap_or(0.5)))),
+        "tent" => Some(Box::new(TentFilter::new(radius.unwrap_or(1.0)))),
+        "gaussian" => Some(Box::new(GaussianFilter::new(radius.unwrap_or(1.5), 2.0))),
+        "mitchell" => Some(Box::new(MitchellFilter::new(radius.unwrap_or(2.0), 1.0 / 3.0, 1.0 / 3.0))),
+        "lanczos" => Some(Box::new(LanczosFilter::new(radius.unwrap_or(3.0), 3.0))),
+        _ => None
+    }
+}
@@ -0,0 +1,32 @@
+use filter::Filter;
+
+pub struct TentFilter
+{
+    pub radius: f64
+}
+
+impl TentFilter
+{
+    pub fn new(radius: f64) -> TentFilter
+    {
+        TentFilter { radius: radius }
+    }
+
+    fn tent(&self, x: f64) -> f64
+    {
+        (1.0 - x.abs() / self.radius).max(0.0)
+    }
+}
+
+impl Filter for TentFilter
+{
+    fn radius(&self) -> f64
+    {
+        self.radius
+    }
+
+    fn evaluate(&self, x: f64, y: f64) -> f64
+    {
+        self.tent(x) * self.tent(y)
+    }
+}
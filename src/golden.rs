@@ -0,0 +1,219 @@
+// Renders small versions of the built-in scenes with a fixed seed and compares them against the reference images in
+// tests/golden. Run with UPDATE_GOLDEN=1 to rewrite the references after an intentional change to the output. On a
+// mismatch the render and an amplified difference image are written to target/golden for inspection.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use lodepng;
+use rgb::RGBA;
+use camera::Camera;
+use camera::perspective::Perspective;
+use camera::shutter::Shutter;
+use film;
+use filter;
+use renderer::Renderer;
+use scene::Scene;
+use scenes;
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 48;
+const RAY_COUNT: usize = 8;
+const BOUNCE_MAX: i32 = 8;
+const SEED: u64 = 1;
+
+const MIN_PSNR: f64 = 40.0;
+const MAX_PIXEL_ERROR: u8 = 16;
+
+struct Comparison
+{
+    rmse: f64,
+    psnr: f64,
+    max_error: u8
+}
+
+fn compare(actual: &[RGBA<u8>], expected: &[RGBA<u8>]) -> Comparison
+{
+    let mut sum = 0.0;
+    let mut max_error = 0;
+    for (a, e) in actual.iter().zip(expected.iter())
+    {
+        for (x, y) in [(a.r, e.r), (a.g, e.g), (a.b, e.b)].iter()
+        {
+            let error = (*x as i32 - *y as i32).unsigned_abs() as u8;
+            sum += (error as f64) * (error as f64);
+            max_error = max_error.max(error);
+        }
+    }
+
+    let rmse = (sum / (actual.len() * 3) as f64).sqrt();
+    let psnr = if rmse == 0.0 { f64::INFINITY } else { 20.0 * (255.0 / rmse).log10() };
+    Comparison { rmse: rmse, psnr: psnr, max_error: max_error }
+}
+
+fn diff_image(actual: &[RGBA<u8>], expected: &[RGBA<u8>]) -> Vec<RGBA<u8>>
+{
+    let diff = |x: u8, y: u8| ((x as i32 - y as i32).unsigned_abs() * 8).min(255) as u8;
+    actual.iter().zip(expected.iter()).map(|(a, e)| RGBA { r: diff(a.r, e.r), g: diff(a.g, e.g), b: diff(a.b, e.b), a: 255 }).collect()
+}
+
+fn render(scene: &Scene, camera: &Camera) -> Vec<RGBA<u8>>
+{
+    let mut renderer = Renderer::new(WIDTH, HEIGHT, RAY_COUNT, BOUNCE_MAX);
+    renderer.seed = SEED;
+    film::to_rgba(&renderer.render(scene, camera, filter::from_name("mitchell", None).unwrap()).film.colors())
+}
+
+fn check<C: Camera>(name: &str, scene: Scene, camera: C)
+{
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let reference_path = root.join("tests").join("golden").join(format!("{}.png", name));
+    let actual = render(&scene, &camera);
+
+    if env::var("UPDATE_GOLDEN").is_ok()
+    {
+        fs::create_dir_all(reference_path.parent().unwrap()).unwrap();
+        lodepng::encode32_file(&reference_path, &actual, WIDTH, HEIGHT).unwrap();
+        return;
+    }
+
+    let expected = lodepng::decode32_file(&reference_path)
+        .unwrap_or_else(|e| panic!("Couldn't load {}: {}, run with UPDATE_GOLDEN=1 to create it", reference_path.display(), e));
+    assert_eq!((expected.width, expected.height), (WIDTH, HEIGHT), "{} reference has the wrong size", name);
+
+    let comparison = compare(&actual, &expected.buffer);
+    if comparison.psnr < MIN_PSNR || comparison.max_error > MAX_PIXEL_ERROR
+    {
+        let output = root.join("target").join("golden");
+        fs::create_dir_all(&output).unwrap();
+        lodepng::encode32_file(output.join(format!("{}.png", name)), &actual, WIDTH, HEIGHT).unwrap();
+        lodepng::encode32_file(output.join(format!("{}.diff.png", name)), &diff_image(&actual, &expected.buffer), WIDTH, HEIGHT).unwrap();
+        panic!("{} differs from its reference: RMSE {:.3}, PSNR {:.2} dB, max pixel error {}, see {}",
+            name, comparison.rmse, comparison.psnr, comparison.max_error, output.display());
+    }
+}
+
+fn build(name: &str) -> (Scene, Perspective)
+{
+    scenes::build(name, (WIDTH as f64) / (HEIGHT as f64)).unwrap()
+}
+
+#[test]
+fn basic()
+{
+    let (scene, camera) = build("basic");
+    check("basic", scene, camera);
+}
+
+#[test]
+fn cornell_box()
+{
+    let (scene, camera) = build("cornell_box");
+    check("cornell_box", scene, camera);
+}
+
+#[test]
+fn showcase()
+{
+    let (scene, camera) = build("showcase");
+    check("showcase", scene, camera);
+}
+
+#[test]
+fn materials()
+{
+    let (scene, camera) = build("materials");
+    check("materials", scene, camera);
+}
+
+#[test]
+fn many_lights()
+{
+    let (scene, camera) = build("many_lights");
+    check("many_lights", scene, camera);
+}
+
+#[test]
+fn triangle_soup()
+{
+    let (scene, camera) = scenes::triangle_soup::build_with_count((WIDTH as f64) / (HEIGHT as f64), 200);
+    check("triangle_soup", scene, camera);
+}
+
+#[test]
+fn motion()
+{
+    let (scene, camera) = build("motion");
+    check("motion", scene, Shutter::new(Box::new(camera), 0.0, 1.0));
+}
+
+#[test]
+fn turntable()
+{
+    // Part way through the animation, between keys of every track.
+    let (scene, camera) = build("turntable");
+    let camera = scenes::camera_path("turntable").unwrap().perspective_at(&camera, 1.3);
+    check("turntable", scene, Shutter::new(Box::new(camera), 1.3, 1.3));
+}
+
+#[test]
+fn mechanical()
+{
+    let (scene, camera) = build("mechanical");
+    check("mechanical", scene, camera);
+}
+
+#[test]
+fn quartics()
+{
+    let (scene, camera) = build("quartics");
+    check("quartics", scene, camera);
+}
+
+#[test]
+fn csg()
+{
+    let (scene, camera) = build("csg");
+    check("csg", scene, camera);
+}
+
+#[test]
+fn sdf()
+{
+    let (scene, camera) = build("sdf");
+    check("sdf", scene, camera);
+}
+
+#[test]
+fn fractals()
+{
+    let (scene, camera) = build("fractals");
+    check("fractals", scene, camera);
+}
+
+#[test]
+fn terrain()
+{
+    let (scene, camera) = build("terrain");
+    check("terrain", scene, camera);
+}
+
+#[test]
+fn identical_images_compare_equal()
+{
+    let image = vec![RGBA { r: 10, g: 20, b: 30, a: 255 }; 4];
+    let comparison = compare(&image, &image);
+    assert_eq!(comparison.rmse, 0.0);
+    assert_eq!(comparison.max_error, 0);
+    assert!(comparison.psnr.is_infinite());
+}
+
+#[test]
+fn differing_images_report_max_error()
+{
+    let expected = vec![RGBA { r: 10, g: 20, b: 30, a: 255 }; 4];
+    let mut actual = expected.clone();
+    actual[2].g = 60;
+    let comparison = compare(&actual, &expected);
+    assert_eq!(comparison.max_error, 40);
+    assert!(comparison.psnr < MIN_PSNR);
+}
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{self, Write, BufWriter};
+
+const PIXEL_TYPE_FLOAT: i32 = 2;
+
+pub struct Channel
+{
+    pub name: String,
+    pub data: Vec<f32>
+}
+
+impl Channel
+{
+    pub fn new(name: &str, data: Vec<f32>) -> Channel
+    {
+        Channel { name: name.to_string(), data: data }
+    }
+}
+
+// Writes an uncompressed, single part, scanline OpenEXR file with one 32-bit float plane per channel.
+// Layers are expressed the usual way, through dotted channel names such as "normal.X".
+pub fn write(path: &str, width: usize, height: usize, mut channels: Vec<Channel>) -> io::Result<()>
+{
+    channels.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut header = Vec::new();
+    let mut channel_list = Vec::new();
+    for channel in channels.iter()
+    {
+        channel_list.extend_from_slice(channel.name.as_bytes());
+        channel_list.push(0);
+        channel_list.extend_from_slice(&PIXEL_TYPE_FLOAT.to_le_bytes());
+        channel_list.extend_from_slice(&[0, 0, 0, 0]);
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+    }
+    channel_list.push(0);
+
+    let mut window = Vec::new();
+    for value in [0, 0, width as i32 - 1, height as i32 - 1].iter()
+    {
+        window.extend_from_slice(&value.to_le_bytes());
+    }
+
+    write_attribute(&mut header, "channels", "chlist", &channel_list);
+    write_attribute(&mut header, "compression", "compression", &[0]);
+    write_attribute(&mut header, "dataWindow", "box2i", &window);
+    write_attribute(&mut header, "displayWindow", "box2i", &window);
+    write_attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+    write_attribute(&mut header, "pixelAspectRatio", "float", &1.0f32.to_le_bytes());
+    write_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+    write_attribute(&mut header, "screenWindowWidth", "float", &1.0f32.to_le_bytes());
+    header.push(0);
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&[0x76, 0x2f, 0x31, 0x01, 2, 0, 0, 0])?;
+    file.write_all(&header)?;
+
+    let line_size = channels.len() * width * 4;
+    let table_end = 8 + header.len() + height * 8;
+    for y in 0..height
+    {
+        let offset = (table_end + y * (8 + line_size)) as u64;
+        file.write_all(&offset.to_le_bytes())?;
+    }
+
+    for y in 0..height
+    {
+        file.write_all(&(y as i32).to_le_bytes())?;
+        file.write_all(&(line_size as i32).to_le_bytes())?;
+        for channel in channels.iter()
+        {
+            for value in &channel.data[y * width..(y + 1) * width]
+            {
+                file.write_all(&value.to_le_bytes())?;
+            }
+        }
+    }
+    file.flush()
+}
+
+fn write_attribute(header: &mut Vec<u8>, name: &str, kind: &str, value: &[u8])
+{
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(kind.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
@@ -0,0 +1,2 @@
+pub mod pfm;
+pub mod exr;
@@ -0,0 +1,35 @@
+use std::fs::File;
+use std::io::{self, Write, BufWriter};
+use vector3::Vector3;
+
+// Portable float map, with rows stored bottom to top and a negative scale marking little endian data.
+pub fn write_color(path: &str, width: usize, height: usize, data: &[Vector3]) -> io::Result<()>
+{
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "PF\n{} {}\n-1.0\n", width, height)?;
+    for y in (0..height).rev()
+    {
+        for color in &data[y * width..(y + 1) * width]
+        {
+            for value in [color.x, color.y, color.z].iter()
+            {
+                file.write_all(&(*value as f32).to_le_bytes())?;
+            }
+        }
+    }
+    file.flush()
+}
+
+pub fn write_gray(path: &str, width: usize, height: usize, data: &[f64]) -> io::Result<()>
+{
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "Pf\n{} {}\n-1.0\n", width, height)?;
+    for y in (0..height).rev()
+    {
+        for value in &data[y * width..(y + 1) * width]
+        {
+            file.write_all(&(*value as f32).to_le_bytes())?;
+        }
+    }
+    file.flush()
+}
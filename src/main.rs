@@ -1,104 +1,398 @@
 extern crate rgb;
 extern crate lodepng;
 extern crate rand;
+mod animation;
+mod aov;
+mod benchmark;
 mod camera;
+#[cfg(test)]
+mod chi_square;
+mod denoise;
+mod distance;
+mod film;
+mod filter;
+#[cfg(test)]
+mod golden;
+mod image;
 mod material;
+mod polynomial;
 mod ray;
+mod random;
 mod renderable;
+mod renderer;
 mod scene;
+mod scenes;
+mod stats;
+mod transform;
 mod vector3;
-use rgb::{RGBA, ByteSlice};
-use camera::Camera;
-use ray::Ray;
-use renderable::plane::Plane;
-use renderable::plane_bounded::PlaneBounded;
-use renderable::sphere::Sphere;
-use renderable::cube::Cube;
-use material::lambert::Lambert;
-use material::metal::Metal;
-use material::dielectric::Dielectric;
-use scene::Scene;
+mod video;
+use std::env;
+use std::time::Instant;
+use aov::AovFormat;
+use camera::{Camera, CameraOptions};
+use camera::aperture::Aperture;
+use camera::lens_system;
+use camera::perspective::Perspective;
+use camera::physical::Physical;
+use camera::shutter::Shutter;
+use camera::stereo::{self, StereoLayout};
+use denoise::Denoiser;
+use renderer::Renderer;
+use stats::Report;
 use vector3::Vector3;
+use video::{VideoWriter, VideoOptions};
 
 
 fn main()
 {
-    const WIDTH: usize = 400;
-    const HEIGHT: usize = 200;
-    const RAY_COUNT: usize = 200;
-    const BOUNCE_MAX: i32 = 100;
+    let mut width: usize = 400;
+    let mut height: usize = 200;
+    let mut ray_count: usize = 200;
+    let mut bounce_max: i32 = 100;
+    let mut scene_name = String::from("basic");
+    let mut heightmap: Option<String> = None;
+    let mut camera_name = String::from("perspective");
+    let mut camera_options = CameraOptions::new();
+    let mut stereo_layout: Option<StereoLayout> = None;
+    let mut interocular = 0.065;
+    let mut convergence: Option<f64> = None;
+    // Any of the physical camera options starts from a 50mm lens at f/16.
+    let default_physical = Physical::new(50.0, 16.0);
+    let mut physical: Option<Physical> = None;
+    let mut focus_distance: Option<f64> = None;
+    let mut aperture: Option<Aperture> = None;
+    let mut cat_eye = 0.0;
+    let mut shutter_open = 0.0;
+    let mut shutter_close: Option<f64> = None;
+    let mut frames: Option<(usize, usize)> = None;
+    let mut fps = 24.0;
+    let mut seed: u64 = 0;
+    let mut filter_name = String::from("mitchell");
+    let mut filter_radius: Option<f64> = None;
+    let mut output = String::from("out.png");
+    let mut video_path: Option<String> = None;
+    let mut loops = 0;
+    let mut dither = false;
+    let mut aov_format: Option<AovFormat> = None;
+    let mut denoiser: Option<Denoiser> = None;
+    let mut keep_raw = false;
+    let mut stats_path: Option<String> = None;
+    let mut clamp_direct: Option<f64> = None;
+    let mut clamp_indirect: Option<f64> = None;
+    let mut debug = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "--scene" => scene_name = args.next().expect("--scene requires a name"),
+            // Raises the terrain scene's hills from an image rather than its own.
+            "--heightmap" => heightmap = Some(args.next().expect("--heightmap requires a path")),
+            "--list-scenes" =>
+            {
+                println!("{}", scenes::NAMES.join("\n"));
+                return;
+            },
+            "--benchmark" =>
+            {
+                let path = args.next().expect("--benchmark requires a path for the JSON results");
+                benchmark::write_json(&path, &benchmark::run()).unwrap();
+                return;
+            },
+            "--camera" => camera_name = args.next().expect("--camera requires a name"),
+            "--camera-fov" => camera_options.fov = args.next().and_then(|v| v.parse().ok()).expect("--camera-fov requires a number"),
+            "--lens-file" =>
+            {
+                let path = args.next().expect("--lens-file requires a path");
+                camera_options.lens = lens_system::load_prescription(&path).unwrap_or_else(|e| panic!("{}", e));
+            },
+            "--stereo" => stereo_layout = Some(match args.next().as_deref()
+            {
+                Some("side-by-side") => StereoLayout::SideBySide,
+                Some("over-under") => StereoLayout::OverUnder,
+                Some("anaglyph") => StereoLayout::Anaglyph,
+                _ => panic!("--stereo requires one of side-by-side, over-under or anaglyph")
+            }),
+            "--interocular" => interocular = args.next().and_then(|v| v.parse().ok()).expect("--interocular requires a number"),
+            "--convergence" => convergence = Some(args.next().and_then(|v| v.parse().ok()).expect("--convergence requires a number")),
+            "--focal-length" =>
+            {
+                let focal_length = args.next().and_then(|v| v.parse().ok()).expect("--focal-length requires a number of millimeters");
+                physical.get_or_insert(default_physical).focal_length = focal_length;
+            },
+            "--sensor" =>
+            {
+                let size: Vec<f64> = args.next().map(|v| v.split('x').filter_map(|n| n.parse().ok()).collect()).unwrap_or_default();
+                assert!(size.len() == 2, "--sensor requires a size in millimeters like 36x24");
+                let p = physical.get_or_insert(default_physical);
+                p.sensor_width = size[0];
+                p.sensor_height = size[1];
+            },
+            "--f-number" =>
+            {
+                let f_number = args.next().and_then(|v| v.parse().ok()).expect("--f-number requires a number");
+                physical.get_or_insert(default_physical).f_number = f_number;
+            },
+            "--shutter" =>
+            {
+                // Shutter times are usually written as fractions of a second, like 1/125.
+                let shutter = args.next().and_then(|v| match v.split_once('/')
+                {
+                    Some((a, b)) => a.parse::<f64>().ok().and_then(|a| b.parse::<f64>().ok().map(|b| a / b)),
+                    None => v.parse().ok()
+                }).expect("--shutter requires a time in seconds, like 1/125");
+                physical.get_or_insert(default_physical).shutter = shutter;
+            },
+            "--iso" =>
+            {
+                let iso = args.next().and_then(|v| v.parse().ok()).expect("--iso requires a number");
+                physical.get_or_insert(default_physical).iso = iso;
+            },
+            "--focus-distance" =>
+            {
+                focus_distance = Some(args.next().and_then(|v| v.parse().ok()).expect("--focus-distance requires a number"));
+                physical.get_or_insert(default_physical);
+            },
+            "--aperture-blades" =>
+            {
+                let blades = args.next().and_then(|v| v.parse().ok()).filter(|&b| b >= 3).expect("--aperture-blades requires a number of at least 3");
+                let rotation = match aperture { Some(Aperture::Polygon { rotation, .. }) => rotation, _ => 90.0 };
+                aperture = Some(Aperture::Polygon { blades: blades, rotation: rotation });
+            },
+            "--aperture-rotation" =>
+            {
+                let rotation = args.next().and_then(|v| v.parse().ok()).expect("--aperture-rotation requires a number of degrees");
+                let blades = match aperture { Some(Aperture::Polygon { blades, .. }) => blades, _ => 6 };
+                aperture = Some(Aperture::Polygon { blades: blades, rotation: rotation });
+            },
+            "--aperture-mask" =>
+            {
+                let path = args.next().expect("--aperture-mask requires a path");
+                aperture = Some(Aperture::load_mask(&path).unwrap_or_else(|e| panic!("{}", e)));
+            },
+            "--shutter-open" => shutter_open = args.next().and_then(|v| v.parse().ok()).expect("--shutter-open requires a time"),
+            "--shutter-close" => shutter_close = Some(args.next().and_then(|v| v.parse().ok()).expect("--shutter-close requires a time")),
+            "--cat-eye" => cat_eye = args.next().and_then(|v| v.parse().ok()).expect("--cat-eye requires a number"),
+            "--width" => width = args.next().and_then(|v| v.parse().ok()).expect("--width requires a number"),
+            "--height" => height = args.next().and_then(|v| v.parse().ok()).expect("--height requires a number"),
+            "--samples" => ray_count = args.next().and_then(|v| v.parse().ok()).expect("--samples requires a number"),
+            "--bounces" => bounce_max = args.next().and_then(|v| v.parse().ok()).expect("--bounces requires a number"),
+            "--frames" =>
+            {
+                // Either a single frame, or an inclusive range like 0-47.
+                let range = args.next().and_then(|v| match v.split_once('-')
+                {
+                    Some((a, b)) => a.parse().ok().and_then(|a| b.parse().ok().map(|b| (a, b))),
+                    None => v.parse().ok().map(|f| (f, f))
+                }).filter(|&(a, b)| a <= b).expect("--frames requires a frame number or a range like 0-47");
+                frames = Some(range);
+            },
+            "--fps" => fps = args.next().and_then(|v| v.parse().ok()).filter(|&f: &f64| f > 0.0).expect("--fps requires a positive number"),
+            "--seed" => seed = args.next().and_then(|v| v.parse().ok()).expect("--seed requires a number"),
+            "--filter" => filter_name = args.next().expect("--filter requires a name"),
+            "--filter-radius" => filter_radius = Some(args.next().and_then(|v| v.parse().ok()).expect("--filter-radius requires a number")),
+            "--output" => output = args.next().expect("--output requires a path"),
+            "--video" =>
+            {
+                let path = args.next().filter(|p| video::EXTENSIONS.iter().any(|e| p.ends_with(&format!(".{}", e))))
+                    .unwrap_or_else(|| panic!("--video requires a path ending in one of: {}", video::EXTENSIONS.join(", ")));
+                video_path = Some(path);
+            },
+            "--loops" => loops = args.next().and_then(|v| v.parse().ok()).expect("--loops requires a number, or 0 to repeat forever"),
+            "--dither" => dither = true,
+            "--aov" => aov_format = Some(match args.next().as_deref()
+            {
+                Some("png") => AovFormat::Png,
+                Some("pfm") => AovFormat::Pfm,
+                Some("exr") => AovFormat::Exr,
+                _ => panic!("--aov requires one of png, pfm or exr")
+            }),
+            "--denoise" => denoiser = Some(Denoiser::new(5, 1.0)),
+            "--denoise-strength" =>
+            {
+                let strength = args.next().and_then(|v| v.parse().ok()).expect("--denoise-strength requires a number");
+                denoiser.get_or_insert(Denoiser::new(5, 1.0)).strength = strength;
+            },
+            "--denoise-iterations" =>
+            {
+                let iterations = args.next().and_then(|v| v.parse().ok()).expect("--denoise-iterations requires a number");
+                denoiser.get_or_insert(Denoiser::new(5, 1.0)).iterations = iterations;
+            },
+            "--keep-raw" => keep_raw = true,
+            "--clamp-direct" => clamp_direct = Some(args.next().and_then(|v| v.parse().ok()).expect("--clamp-direct requires a number")),
+            "--clamp-indirect" => clamp_indirect = Some(args.next().and_then(|v| v.parse().ok()).expect("--clamp-indirect requires a number")),
+            "--debug" => debug = true,
+            "--stats-json" => stats_path = Some(args.next().expect("--stats-json requires a path")),
+            _ => panic!("Unknown argument: {}", arg)
+        }
+    }
+    let mut report = Report::new();
+    let mut phase_start = Instant::now();
+    // Phases repeat for every frame of a sequence, and add up.
+    let mut end_phase = |report: &mut Report, name: &'static str|
+    {
+        match report.phases.iter_mut().find(|&&mut (n, _)| n == name)
+        {
+            Some(phase) => phase.1 += phase_start.elapsed(),
+            None => report.phases.push((name, phase_start.elapsed()))
+        }
+        phase_start = Instant::now();
+    };
 
-    let mut scene = Scene::new();
-    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.4, 0.8, 0.4))));
-    scene.add(PlaneBounded::new(Vector3::new(0.0, 0.25, 0.5), Vector3::new(-0.25, 0.5, 0.0), 0.5, 0.25, Lambert::new(Vector3::new(0.4, 0.4, 0.8))));
-    scene.add(Sphere::new(Vector3::new(-1.0, 0.5, -1.0), 0.5, Metal::new(Vector3::new(0.8, 0.6, 0.2), 0.2)));
-    scene.add(Sphere::new(Vector3::new(1.0, 0.5, 0.2), 0.35, Metal::new(Vector3::new(0.8, 0.2, 0.6), 0.05)));
-    scene.add(Cube::new(Vector3::new(-0.25, 0.5, -0.2), 0.5, 0.5, 0.5, Lambert::new(Vector3::new(0.8, 0.0, 0.0))));
-    scene.add(Cube::new(Vector3::new(0.5, 0.5, 0.5), 1.0, 0.5, 0.5, Dielectric::new(0.5)));
+    let filter = || filter::from_name(&filter_name, filter_radius).expect("Unknown filter");
+    // A physical camera's shutter time is how long moving objects blur for as well, unless the shutter closes at a
+    // time of its own.
+    let shutter_close = shutter_close.unwrap_or(shutter_open + physical.map_or(0.0, |p| p.shutter));
+    let aspect = (width as f64) / (height as f64);
+    let built = match heightmap
+    {
+        Some(ref path) =>
+        {
+            assert!(scene_name == "terrain", "--heightmap only applies to the terrain scene");
+            Some(scenes::terrain::build_with_heightmap(aspect, path).unwrap_or_else(|e| panic!("{}", e)))
+        },
+        None => scenes::build(&scene_name, aspect)
+    };
+    let (scene, scene_camera) = built
+        .unwrap_or_else(|| panic!("Unknown scene {}, expected one of: {}", scene_name, scenes::NAMES.join(", ")));
+    let camera_path = scenes::camera_path(&scene_name);
+    let unknown_camera = format!("Unknown camera {}, expected one of: {}, or ods for stereo", camera_name, camera::NAMES.join(", "));
 
-    let camera_origin = Vector3{x: -0.75, y: 1.2, z: 1.0};
-    let camera_look_at = Vector3{x: 0.0, y: 0.5, z: 0.0};
-    let camera_up = Vector3{x: 0.0, y: 1.0, z:0.0};
-    let camera = Camera::new(
-        camera_origin, camera_look_at,
-        camera_up, 72.0, (WIDTH as f64) / (HEIGHT as f64), 0.0, (camera_origin - camera_look_at).length());
+    // The omni-directional stereo camera only makes sense as an over-under pair of panoramas.
+    if camera_name == "ods"
+    {
+        stereo_layout = Some(StereoLayout::OverUnder);
+    }
+    let prefix = output.trim_end_matches(".png").to_string();
+    end_phase(&mut report, "scene");
 
-    let mut pixel_data = [RGBA{r: 0, g: 0, b: 0, a: 255}; WIDTH * HEIGHT];
+    // Frames of a video are written as they're rendered, once the size of the first one is known.
+    let mut video: Option<Box<VideoWriter>> = None;
+    let mut video_options = VideoOptions::new(fps);
+    video_options.loops = loops;
+    video_options.dither = dither;
 
-    for y in 0..HEIGHT
+    // A single image is frame zero, at time zero.
+    let (first_frame, last_frame) = frames.unwrap_or((0, 0));
+    for frame in first_frame..last_frame + 1
     {
-        for x in 0..WIDTH
+        let time = frame as f64 / fps;
+        let prefix = match frames
         {
-            let mut color = vector3::ZERO;
-            for _ in 0..RAY_COUNT
-            {
-                let u = ((x as f64) + rand::random::<f64>()) / (WIDTH as f64);
-                let v = ((y as f64) + rand::random::<f64>()) / (HEIGHT as f64);
-                let ray = camera.get_ray(u, v);
-                color += get_color(ray, &scene, BOUNCE_MAX);
-            }
-            color /= RAY_COUNT as f64;
-            color = Vector3{x: color.x.sqrt(), y: color.y.sqrt(), z: color.z.sqrt()};
+            Some(_) => format!("{}.{:04}", prefix, frame),
+            None => prefix.clone()
+        };
 
-            pixel_data[(HEIGHT - 1 - y) * WIDTH + x] = RGBA
+        let mut perspective = match camera_path
+        {
+            Some(ref path) => path.perspective_at(&scene_camera, time),
+            None => scene_camera.clone()
+        };
+        if let Some(ref p) = physical
+        {
+            camera_options.film_width = p.sensor_width;
+            let focus_distance = focus_distance.unwrap_or(perspective.focus_distance);
+            perspective = Perspective::physical(perspective.origin, perspective.origin - perspective.w, perspective.v, aspect, p, focus_distance);
+        }
+        if let Some(ref a) = aperture
+        {
+            perspective.aperture = a.clone();
+        }
+        perspective.cat_eye = cat_eye;
+
+        // Each view is rendered separately, and its AOVs and raw image get their own files.
+        let views: Vec<(&str, Box<Camera>)> = match stereo_layout
+        {
+            Some(_) =>
             {
-                r: (color.x * 255.0) as u8, 
-                g: (color.y * 255.0) as u8,
-                b: (color.z * 255.0) as u8, 
-                a: 255
-            };
+                let convergence = convergence.unwrap_or(perspective.focus_distance);
+                let (left, right) = stereo::eyes(&camera_name, perspective, &camera_options, aspect, interocular, convergence)
+                    .expect(&unknown_camera);
+                vec![(".left", left), (".right", right)]
+            },
+            None => vec![("", camera::from_name(&camera_name, perspective, &camera_options, aspect).expect(&unknown_camera))]
+        };
+        // The shutter opens and closes relative to the frame's time, and moving objects are placed by the time of
+        // each ray.
+        let views: Vec<(&str, Box<Camera>)> = views.into_iter()
+            .map(|(suffix, camera)| (suffix, Box::new(Shutter::new(camera, time + shutter_open, time + shutter_close)) as Box<Camera>))
+            .collect();
+        end_phase(&mut report, "scene");
+
+        let mut renderer = Renderer::new(width, height, ray_count, bounce_max);
+        renderer.aovs = aov_format.is_some() || denoiser.is_some();
+        // Noise that stayed the same from frame to frame would look like dirt on the lens.
+        renderer.seed = seed.wrapping_add(frame as u64);
+        renderer.clamp_direct = clamp_direct;
+        renderer.clamp_indirect = clamp_indirect;
+        renderer.debug = debug;
+        let results: Vec<_> = views.iter().map(|(_, camera)|
+        {
+            renderer.render(&scene, &**camera, filter())
+        }).collect();
+        let mut colors: Vec<Vec<Vector3>> = results.iter().map(|r| r.film.colors()).collect();
+        for result in results.iter()
+        {
+            report.counters.merge(&result.counters);
         }
-    }
+        end_phase(&mut report, "render");
 
-    lodepng::encode32_file("out.png", &pixel_data.as_bytes(), WIDTH, HEIGHT).unwrap();
-}
+        // Raw images are kept until the exposure is applied, so they only differ from the final ones by the denoising.
+        let mut raw: Vec<Vec<Vector3>> = Vec::new();
+        if let Some(ref d) = denoiser
+        {
+            for (i, result) in results.iter().enumerate()
+            {
+                if keep_raw
+                {
+                    raw.push(colors[i].clone());
+                }
+                colors[i] = d.denoise(&colors[i], result.aovs.as_ref().unwrap());
+            }
+            end_phase(&mut report, "denoise");
+        }
 
-fn get_color(ray: Ray, scene: &Scene, bounce_max: i32) -> Vector3
-{
-    let hit_result = scene.test_hit(ray);
-    match hit_result
-    {
-        None => {},
-        Some(h) =>
+        if let Some(ref p) = physical
         {
-            if bounce_max < 0
+            let exposure = p.exposure();
+            for c in colors.iter_mut().chain(raw.iter_mut()).flat_map(|c| c.iter_mut())
             {
-                return vector3::ZERO;
+                *c *= exposure;
             }
+        }
+        for (i, r) in raw.iter().enumerate()
+        {
+            lodepng::encode32_file(format!("{}{}.raw.png", prefix, views[i].0), &film::to_rgba(r), width, height).unwrap();
+        }
 
-            let scatter_result = h.material.scatter(ray, h);
-            match scatter_result
+        let (image, image_width, image_height) = match stereo_layout
+        {
+            Some(layout) => stereo::compose(layout, &colors[0], &colors[1], width, height),
+            None => (colors[0].clone(), width, height)
+        };
+        let rgba = film::to_rgba(&image);
+        match video_path
+        {
+            Some(ref path) => video.get_or_insert_with(|| video::create(path, image_width, image_height, &video_options).unwrap()).write_frame(&rgba).unwrap(),
+            None => lodepng::encode32_file(format!("{}.png", prefix), &rgba, image_width, image_height).unwrap()
+        }
+        if let Some(format) = aov_format
+        {
+            for (i, result) in results.iter().enumerate()
             {
-                None => {},
-                Some(s) =>
-                {
-                    return s.attenuation * get_color(s.scattered, scene, bounce_max - 1);
-                }
+                result.aovs.as_ref().unwrap().write(&format!("{}{}", prefix, views[i].0), format, &colors[i]).unwrap();
             }
         }
+        end_phase(&mut report, "output");
+    }
+    if let Some(ref mut v) = video
+    {
+        v.finish().unwrap();
     }
 
-    let direction = ray.direction.normalized();
-    let t = 0.5 * (direction.y + 1.0);
-    (1.0 - t) * vector3::ONE + t * Vector3{x: 0.5, y: 0.7, z: 1.0}
+    report.print();
+    if let Some(path) = stats_path
+    {
+        report.write_json(&path).unwrap();
+    }
 }
@@ -0,0 +1,40 @@
+use animation::Track;
+use vector3::Vector3;
+use ray::Ray;
+use renderable::HitResult;
+use material::{Material, ScatterResult};
+
+// Tints another material by a color that changes over time. Give the material a white albedo or emission to animate
+// its color outright.
+#[derive(Clone)]
+pub struct Animated<M: Material>
+{
+    pub material: M,
+    pub color: Track<Vector3>
+}
+
+impl<M: Material> Animated<M>
+{
+    pub fn new(material: M, color: Track<Vector3>) -> Animated<M>
+    {
+        Animated { material: material, color: color }
+    }
+}
+
+impl<M: Material> Material for Animated<M>
+{
+    fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>
+    {
+        self.material.scatter(ray, hit_result).map(|s| ScatterResult { scattered: s.scattered, attenuation: s.attenuation * self.color.value_at(ray.time) })
+    }
+
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        self.material.albedo(hit_result) * self.color.value_at(hit_result.time)
+    }
+
+    fn emitted(&self, hit_result: HitResult) -> Vector3
+    {
+        self.material.emitted(hit_result) * self.color.value_at(hit_result.time)
+    }
+}
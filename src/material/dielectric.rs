@@ -1,9 +1,10 @@
-extern crate rand;
-use vector3::Vector3;
+use random;
+use vector3::{ONE, Vector3};
 use ray::Ray;
 use renderable::HitResult;
 use material::{Material, ScatterResult, reflect, refract, schlick};
 
+#[derive(Clone)]
 pub struct Dielectric
 {
     pub refraction: f64
@@ -30,7 +31,9 @@ impl Material for Dielectric
         {
             outward_normal = -hit_result.normal;
             ni_over_nt = self.refraction;
-            cosine = self.refraction * ray.direction.dot(hit_result.normal) / ray.direction.length();
+            // Schlick's approximation wants the angle on the outside of the surface, which is the refracted one here.
+            let inside_cosine = ray.direction.dot(hit_result.normal) / ray.direction.length();
+            cosine = (1.0 - self.refraction * self.refraction * (1.0 - inside_cosine * inside_cosine)).max(0.0).sqrt();
         }
         else
         {
@@ -45,11 +48,11 @@ impl Material for Dielectric
             None => {},
             Some(v) =>
             {
-                if rand::random::<f64>() > schlick(cosine, self.refraction)
+                if random::next_f64() > schlick(cosine, self.refraction)
                 {
                     return Some(ScatterResult
                     {
-                        scattered: Ray{origin: hit_result.origin, direction: v},
+                        scattered: Ray{origin: hit_result.origin, direction: v, time: ray.time},
                         attenuation: Vector3{x: 1.0, y: 1.0, z: 1.0}
                     });
                 }
@@ -58,8 +61,14 @@ impl Material for Dielectric
 
         Some(ScatterResult
         {
-            scattered: Ray{origin: hit_result.origin, direction: reflected},
+            scattered: Ray{origin: hit_result.origin, direction: reflected, time: ray.time},
             attenuation: Vector3{x: 1.0, y: 1.0, z: 1.0}
         })
     }
+
+    #[allow(unused_variables)]
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        ONE
+    }
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use vector3::{ZERO, Vector3};
+use ray::Ray;
+use renderable::HitResult;
+use material::{Material, ScatterResult};
+
+#[derive(Clone)]
+pub struct DiffuseLight
+{
+    pub emission: Vector3
+}
+
+impl DiffuseLight
+{
+    pub fn new(emission: Vector3) -> DiffuseLight
+    {
+        DiffuseLight { emission: emission }
+    }
+}
+
+impl Material for DiffuseLight
+{
+    #[allow(unused_variables)]
+    fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>
+    {
+        None
+    }
+
+    #[allow(unused_variables)]
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        ZERO
+    }
+
+    #[allow(unused_variables)]
+    fn emitted(&self, hit_result: HitResult) -> Vector3
+    {
+        self.emission
+    }
+}
@@ -1,8 +1,9 @@
 use vector3::Vector3;
 use ray::Ray;
 use renderable::HitResult;
-use material::{Material, ScatterResult, random_in_unit_sphere};
+use material::{Material, ScatterResult, random_unit_vector};
 
+#[derive(Clone)]
 pub struct Lambert
 {
     pub albedo: Vector3
@@ -21,10 +22,17 @@ impl Material for Lambert
     #[allow(unused_variables)]
     fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>
     {
-        let target = hit_result.origin + hit_result.normal + random_in_unit_sphere();
+        // Offsetting the normal by a point on the unit sphere gives a cosine weighted direction.
+        let target = hit_result.origin + hit_result.normal + random_unit_vector();
         Some(ScatterResult{
-            scattered: Ray{origin: hit_result.origin, direction: target - hit_result.origin},
+            scattered: Ray{origin: hit_result.origin, direction: target - hit_result.origin, time: ray.time},
             attenuation: self.albedo
         })
     }
+
+    #[allow(unused_variables)]
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        self.albedo
+    }
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ use ray::Ray;
 use renderable::HitResult;
 use material::{Material, ScatterResult, reflect, random_in_unit_sphere};
 
+#[derive(Clone)]
 pub struct Metal
 {
     pub albedo: Vector3,
@@ -24,7 +25,7 @@ impl Material for Metal
 
         let reflected = reflect(ray.direction.normalized(), hit_result.normal);
         let result = ScatterResult{
-            scattered: Ray{origin: hit_result.origin, direction: reflected + self.fuzz * random_in_unit_sphere()},
+            scattered: Ray{origin: hit_result.origin, direction: reflected + self.fuzz * random_in_unit_sphere(), time: ray.time},
             attenuation: self.albedo
         };
 
@@ -35,4 +36,10 @@ impl Material for Metal
 
         None
     }
+
+    #[allow(unused_variables)]
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        self.albedo
+    }
 }
\ No newline at end of file
@@ -1,9 +1,14 @@
 pub mod lambert;
 pub mod metal;
 pub mod dielectric;
+pub mod diffuse_light;
+pub mod animated;
+pub mod ramp;
+#[cfg(test)]
+mod tests;
 
-extern crate rand;
-use vector3::{ONE, Vector3};
+use random;
+use vector3::{ZERO, ONE, Vector3};
 use ray::Ray;
 use renderable::HitResult;
 
@@ -14,9 +19,16 @@ pub struct ScatterResult
     pub attenuation: Vector3
 }
 
-pub trait Material
+pub trait Material: Sync
 {
     fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>;
+    fn albedo(&self, hit_result: HitResult) -> Vector3;
+
+    #[allow(unused_variables)]
+    fn emitted(&self, hit_result: HitResult) -> Vector3
+    {
+        ZERO
+    }
 }
 
 fn reflect(v: Vector3, n: Vector3) -> Vector3
@@ -50,10 +62,15 @@ fn random_in_unit_sphere() -> Vector3
 {
     loop
     {
-        let vec = 2.0 * Vector3{x: rand::random::<f64>(), y: rand::random::<f64>(), z: rand::random::<f64>()} - ONE;
-        if vec.length_sqr() >= 1.0
+        let vec = 2.0 * Vector3{x: random::next_f64(), y: random::next_f64(), z: random::next_f64()} - ONE;
+        if vec.length_sqr() < 1.0
         {
             return vec;
         }
     }
+}
+
+fn random_unit_vector() -> Vector3
+{
+    random_in_unit_sphere().normalized()
 }
\ No newline at end of file
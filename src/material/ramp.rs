@@ -0,0 +1,40 @@
+use animation::Track;
+use vector3::Vector3;
+use ray::Ray;
+use renderable::HitResult;
+use material::{Material, ScatterResult};
+
+// Tints another material by a color looked up along a ramp by the first surface coordinate of the hit, such as the
+// orbit trap of a fractal. The ramp is a track, with the coordinate standing in for time.
+#[derive(Clone)]
+pub struct Ramp<M: Material>
+{
+    pub material: M,
+    pub colors: Track<Vector3>
+}
+
+impl<M: Material> Ramp<M>
+{
+    pub fn new(material: M, colors: Track<Vector3>) -> Ramp<M>
+    {
+        Ramp { material: material, colors: colors }
+    }
+}
+
+impl<M: Material> Material for Ramp<M>
+{
+    fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>
+    {
+        self.material.scatter(ray, hit_result).map(|s| ScatterResult { scattered: s.scattered, attenuation: s.attenuation * self.colors.value_at(hit_result.uv.0) })
+    }
+
+    fn albedo(&self, hit_result: HitResult) -> Vector3
+    {
+        self.material.albedo(hit_result) * self.colors.value_at(hit_result.uv.0)
+    }
+
+    fn emitted(&self, hit_result: HitResult) -> Vector3
+    {
+        self.material.emitted(hit_result) * self.colors.value_at(hit_result.uv.0)
+    }
+}
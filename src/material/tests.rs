@@ -0,0 +1,233 @@
+use ray::Ray;
+use random;
+use renderable::HitResult;
+use renderable::sphere::Sphere;
+use renderer::get_color;
+use scene::{Scene, Background};
+use chi_square::{assert_fits, assert_same_distribution, direction_bin, histogram};
+use vector3::{ZERO, ONE, Vector3};
+use material::{Material, random_in_unit_sphere, random_unit_vector, reflect, refract, schlick};
+use material::lambert::Lambert;
+use material::metal::Metal;
+use material::dielectric::Dielectric;
+
+const SAMPLES: usize = 100000;
+
+fn hit(material: &Material, normal: Vector3) -> HitResult
+{
+    HitResult { origin: ZERO, normal: normal.normalized(), t: 1.0, time: 0.0, uv: (0.0, 0.0), material: material }
+}
+
+fn incoming(direction: Vector3) -> Ray
+{
+    Ray { origin: -direction, direction: direction.normalized(), time: 0.0 }
+}
+
+fn cosine_bins(bins: usize, phi_bins: usize) -> Vec<f64>
+{
+    let mut expected = Vec::new();
+    for i in 0..bins
+    {
+        let (a, b) = (i as f64 / bins as f64, (i + 1) as f64 / bins as f64);
+        for _ in 0..phi_bins
+        {
+            expected.push((b * b - a * a) / phi_bins as f64);
+        }
+    }
+    expected
+}
+
+#[test]
+fn random_in_unit_sphere_is_uniform_in_the_ball()
+{
+    random::seed(1);
+    let counts = histogram(64, SAMPLES, ||
+    {
+        let v = random_in_unit_sphere();
+        assert!(v.length_sqr() < 1.0);
+        let shell = ((v.length_sqr() * v.length()) * 4.0) as usize;
+        direction_bin(v, Vector3::new(0.0, 0.0, 1.0), 4, 4, false).map(|bin| shell * 16 + bin)
+    });
+    assert_fits("random_in_unit_sphere", &counts, &[1.0 / 64.0; 64]);
+}
+
+#[test]
+fn random_unit_vector_is_uniform_on_the_sphere()
+{
+    random::seed(2);
+    let counts = histogram(64, SAMPLES, ||
+    {
+        let v = random_unit_vector();
+        assert!((v.length() - 1.0).abs() < 1e-9);
+        direction_bin(v, Vector3::new(0.3, -0.2, 0.9), 8, 8, false)
+    });
+    assert_fits("random_unit_vector", &counts, &[1.0 / 64.0; 64]);
+}
+
+#[test]
+fn lambert_scatters_cosine_weighted()
+{
+    random::seed(3);
+    let lambert = Lambert::new(ONE);
+    let normal = Vector3::new(0.3, 0.8, -0.5).normalized();
+    let counts = histogram(32, SAMPLES, ||
+    {
+        let s = lambert.scatter(incoming(-normal), hit(&lambert, normal)).unwrap();
+        direction_bin(s.scattered.direction, normal, 8, 4, true)
+    });
+    assert_fits("Lambert", &counts, &cosine_bins(8, 4));
+}
+
+#[test]
+fn lambert_scattering_is_reciprocal()
+{
+    // A Lambertian BRDF is constant, so where light leaves must not depend on where it came from.
+    random::seed(4);
+    let lambert = Lambert::new(ONE);
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    let sample = |direction: Vector3| histogram(32, SAMPLES, ||
+    {
+        let s = lambert.scatter(incoming(direction), hit(&lambert, normal)).unwrap();
+        direction_bin(s.scattered.direction, normal, 8, 4, true)
+    });
+    let head_on = sample(Vector3::new(0.0, -1.0, 0.0));
+    let grazing = sample(Vector3::new(1.0, -0.1, 0.3));
+    assert_same_distribution("Lambert reciprocity", &head_on, &grazing);
+}
+
+#[test]
+fn metal_scatters_around_the_reflection()
+{
+    random::seed(5);
+    let fuzz = 0.4;
+    let metal = Metal::new(ONE, fuzz);
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    let direction = Vector3::new(1.0, -1.0, 0.0).normalized();
+    let reflected = reflect(direction, normal);
+
+    let actual = histogram(32, SAMPLES, ||
+    {
+        metal.scatter(incoming(direction), hit(&metal, normal))
+            .and_then(|s| direction_bin(s.scattered.direction, reflected, 8, 4, false))
+    });
+
+    // An independent sampler for a point in a ball around the reflection, built from the radius's CDF and a
+    // uniformly distributed direction.
+    let expected = histogram(32, SAMPLES, ||
+    {
+        let radius = random::next_f64().cbrt();
+        let z = 2.0 * random::next_f64() - 1.0;
+        let phi = 2.0 * ::std::f64::consts::PI * random::next_f64();
+        let r = (1.0 - z * z).sqrt();
+        let offset = radius * Vector3::new(r * phi.cos(), r * phi.sin(), z);
+        let scattered = reflected + fuzz * offset;
+        if scattered.dot(normal) > 0.0 { direction_bin(scattered, reflected, 8, 4, false) } else { None }
+    });
+    assert_same_distribution("Metal", &actual, &expected);
+}
+
+#[test]
+fn metal_reflection_is_reciprocal()
+{
+    let metal = Metal::new(ONE, 0.0);
+    let normal = Vector3::new(0.2, 1.0, 0.1).normalized();
+    let direction = Vector3::new(1.0, -0.6, 0.4).normalized();
+    let out = metal.scatter(incoming(direction), hit(&metal, normal)).unwrap().scattered.direction;
+    let back = metal.scatter(incoming(-out), hit(&metal, normal)).unwrap().scattered.direction;
+    assert!((back + direction).length() < 1e-9);
+}
+
+#[test]
+fn dielectric_reflects_with_schlick_probability()
+{
+    random::seed(6);
+    let refraction = 1.5;
+    let glass = Dielectric::new(refraction);
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    let direction = Vector3::new(2.0, -1.0, 0.0).normalized();
+    let reflectance = schlick(-direction.dot(normal), refraction);
+
+    let counts = histogram(2, SAMPLES, ||
+    {
+        let s = glass.scatter(incoming(direction), hit(&glass, normal)).unwrap();
+        Some(if s.scattered.direction.dot(normal) > 0.0 { 0 } else { 1 })
+    });
+    assert_fits("Dielectric Fresnel", &counts, &[reflectance, 1.0 - reflectance]);
+}
+
+#[test]
+fn dielectric_is_reciprocal()
+{
+    // Light refracted into the glass must be able to retrace its path out, and be reflected equally often both ways.
+    random::seed(7);
+    let glass = Dielectric::new(1.5);
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    let direction = Vector3::new(1.5, -1.0, 0.4).normalized();
+    let inside = refract(direction, normal, 1.0 / 1.5).unwrap().normalized();
+    let outside = refract(-inside, -normal, 1.5).unwrap().normalized();
+    assert!((outside + direction).length() < 1e-9);
+
+    let entering = histogram(2, SAMPLES, ||
+    {
+        let s = glass.scatter(incoming(direction), hit(&glass, normal)).unwrap();
+        Some(if s.scattered.direction.dot(normal) > 0.0 { 0 } else { 1 })
+    });
+    let leaving = histogram(2, SAMPLES, ||
+    {
+        let s = glass.scatter(incoming(-inside), hit(&glass, normal)).unwrap();
+        Some(if s.scattered.direction.dot(normal) < 0.0 { 0 } else { 1 })
+    });
+    assert_same_distribution("Dielectric reciprocity", &entering, &leaving);
+}
+
+#[test]
+fn dielectric_reflects_almost_everything_near_the_critical_angle()
+{
+    // Just short of total internal reflection the refracted ray grazes the surface, so nearly all light reflects.
+    random::seed(9);
+    let refraction = 1.5;
+    let glass = Dielectric::new(refraction);
+    let normal = Vector3::new(0.0, 1.0, 0.0);
+    let sine = 0.999 / refraction;
+    let direction = Vector3::new(sine, (1.0 - sine * sine).sqrt(), 0.0);
+    let reflectance = schlick((1.0 - 0.999 * 0.999f64).sqrt(), refraction);
+    assert!(reflectance > 0.75);
+
+    let counts = histogram(2, SAMPLES, ||
+    {
+        let s = glass.scatter(incoming(direction), hit(&glass, normal)).unwrap();
+        Some(if s.scattered.direction.dot(normal) < 0.0 { 0 } else { 1 })
+    });
+    assert_fits("Dielectric near the critical angle", &counts, &[reflectance, 1.0 - reflectance]);
+}
+
+// Renders a sphere in a uniformly white environment, which every material should reflect no more than all of.
+fn furnace<T: Material + 'static>(material: T) -> f64
+{
+    let mut scene = Scene::new();
+    scene.background = Background::Color(ONE);
+    scene.add(Sphere::new(ZERO, 1.0, material));
+
+    let count = 20000;
+    let mut total = 0.0;
+    for _ in 0..count
+    {
+        let target = 0.99 * random_in_unit_sphere();
+        let origin = 3.0 * random_unit_vector();
+        let color = get_color(Ray { origin: origin, direction: target - origin, time: 0.0 }, &scene, 50);
+        assert!(color.x <= 1.0 + 1e-9 && color.y <= 1.0 + 1e-9 && color.z <= 1.0 + 1e-9);
+        total += color.x;
+    }
+    total / count as f64
+}
+
+#[test]
+fn white_furnace()
+{
+    random::seed(8);
+    assert!((furnace(Lambert::new(ONE)) - 1.0).abs() < 1e-9);
+    assert!((furnace(Lambert::new(Vector3::new(0.5, 0.5, 0.5))) - 0.5).abs() < 1e-9);
+    assert!((furnace(Metal::new(ONE, 0.0)) - 1.0).abs() < 1e-9);
+    assert!(furnace(Metal::new(ONE, 0.5)) <= 1.0);
+    assert!((furnace(Dielectric::new(1.5)) - 1.0).abs() < 1e-9);
+}
@@ -0,0 +1,159 @@
+// Real roots of polynomials of any degree, given by their coefficients from the constant term up.
+//
+// Closed forms for cubics and quartics lose most of their precision when roots are close together, which is exactly
+// what happens when a ray grazes a surface. Instead, the roots of the derivative split the line into stretches where
+// the polynomial only rises or only falls, and each stretch that changes sign holds exactly one root, which is then
+// found by Newton's method kept inside the stretch by bisection.
+
+pub fn evaluate(coefficients: &[f64], x: f64) -> f64
+{
+    coefficients.iter().rev().fold(0.0, |sum, &c| sum * x + c)
+}
+
+pub fn derivative(coefficients: &[f64]) -> Vec<f64>
+{
+    coefficients.iter().enumerate().skip(1).map(|(power, &c)| c * power as f64).collect()
+}
+
+// Multiplies two polynomials together.
+pub fn multiply(a: &[f64], b: &[f64]) -> Vec<f64>
+{
+    let mut product = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate()
+    {
+        for (j, &y) in b.iter().enumerate()
+        {
+            product[i + j] += x * y;
+        }
+    }
+    product
+}
+
+// The real roots between low and high in increasing order. Roots where the polynomial only touches zero without
+// crossing it may be missed, as rounding decides whether they are there at all.
+pub fn roots(coefficients: &[f64], low: f64, high: f64) -> Vec<f64>
+{
+    // Leading coefficients that are only rounding error would put roots out near infinity, so they're dropped.
+    let largest = coefficients.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+    let degree = match coefficients.iter().rposition(|c| c.abs() > 1e-12 * largest)
+    {
+        Some(d) => d,
+        None => return Vec::new()
+    };
+    let coefficients = &coefficients[..degree + 1];
+    if degree == 0
+    {
+        return Vec::new();
+    }
+    if degree == 1
+    {
+        let root = -coefficients[0] / coefficients[1];
+        return if root >= low && root <= high { vec![root] } else { Vec::new() };
+    }
+
+    // Every root lies within Cauchy's bound, which keeps the search finite however wide the range asked for is.
+    let bound = 1.0 + coefficients[..degree].iter().fold(0.0f64, |m, c| m.max((c / coefficients[degree]).abs()));
+    let (low, high) = (low.max(-bound), high.min(bound));
+    if low > high
+    {
+        return Vec::new();
+    }
+
+    let slope = derivative(coefficients);
+    let mut ends = vec![low];
+    ends.extend(roots(&slope, low, high));
+    ends.push(high);
+
+    let mut found: Vec<f64> = Vec::new();
+    for pair in ends.windows(2)
+    {
+        let (a, b) = (pair[0], pair[1]);
+        let (fa, fb) = (evaluate(coefficients, a), evaluate(coefficients, b));
+        let root = if fa == 0.0
+        {
+            Some(a)
+        }
+        else if (fa < 0.0) != (fb < 0.0) && fb != 0.0
+        {
+            Some(refine(coefficients, &slope, a, b, fa))
+        }
+        else
+        {
+            None
+        };
+        if let Some(root) = root
+        {
+            if found.last().is_none_or(|&last| root > last)
+            {
+                found.push(root);
+            }
+        }
+    }
+    if evaluate(coefficients, high) == 0.0 && found.last().is_none_or(|&last| high > last)
+    {
+        found.push(high);
+    }
+    found
+}
+
+// Finds the one root between a and b, where the polynomial has the sign of fa at a and the opposite sign at b.
+fn refine(coefficients: &[f64], slope: &[f64], a: f64, b: f64, fa: f64) -> f64
+{
+    let (mut a, mut b) = (a, b);
+    let mut x = (a + b) / 2.0;
+    for _ in 0..200
+    {
+        let f = evaluate(coefficients, x);
+        if f == 0.0
+        {
+            return x;
+        }
+        if (f < 0.0) == (fa < 0.0) { a = x } else { b = x }
+
+        let newton = x - f / evaluate(slope, x);
+        let next = if newton > a && newton < b { newton } else { (a + b) / 2.0 };
+        if (next - x).abs() <= 1e-15 * (1.0 + x.abs()) || b - a <= 1e-15 * (1.0 + x.abs())
+        {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests
+{
+    use polynomial::{evaluate, multiply, roots};
+
+    #[test]
+    fn finds_every_root_in_order()
+    {
+        // (x + 2)(x - 0.5)(x - 1)(x - 3)
+        let quartic = multiply(&multiply(&[2.0, 1.0], &[-0.5, 1.0]), &multiply(&[-1.0, 1.0], &[-3.0, 1.0]));
+        let found = roots(&quartic, -10.0, 10.0);
+        assert_eq!(found.len(), 4);
+        for (root, expected) in found.iter().zip([-2.0, 0.5, 1.0, 3.0].iter())
+        {
+            assert!((root - expected).abs() < 1e-12, "{} isn't {}", root, expected);
+        }
+        assert_eq!(roots(&quartic, 0.0, 2.0).len(), 2);
+        // x² + 1 never reaches zero.
+        assert!(roots(&[1.0, 0.0, 1.0], -1e300, 1e300).is_empty());
+    }
+
+    #[test]
+    fn separates_roots_close_together()
+    {
+        // Two roots a millionth apart, as when a ray only just grazes a surface, alongside two well apart.
+        let close = multiply(&[-(1.0 - 1e-6), 1.0], &[-1.0, 1.0]);
+        let quartic = multiply(&close, &multiply(&[5.0, 1.0], &[-7.0, 1.0]));
+        let found = roots(&quartic, -100.0, 100.0);
+        assert_eq!(found.len(), 4);
+        assert!((found[1] - (1.0 - 1e-6)).abs() < 1e-9 && (found[2] - 1.0).abs() < 1e-9, "{:?}", found);
+        for root in found.iter()
+        {
+            assert!(evaluate(&quartic, *root).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::cell::RefCell;
+use rand::{self, SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+
+// Every random number used while rendering comes from this per-thread generator, so that reseeding it at the
+// start of each tile makes a render repeatable regardless of which thread picks the tile up.
+thread_local!(static RNG: RefCell<SmallRng> = RefCell::new(SmallRng::seed_from_u64(rand::random::<u64>())));
+
+pub fn seed(seed: u64)
+{
+    RNG.with(|rng| *rng.borrow_mut() = SmallRng::seed_from_u64(seed));
+}
+
+pub fn next_f64() -> f64
+{
+    RNG.with(|rng| rng.borrow_mut().random::<f64>())
+}
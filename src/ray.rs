@@ -4,7 +4,9 @@ use vector3::Vector3;
 pub struct Ray
 {
     pub origin: Vector3,
-    pub direction: Vector3
+    pub direction: Vector3,
+    // When the ray was cast, between the camera shutter opening and closing, which moving objects are placed at.
+    pub time: f64
 }
 
 impl Ray
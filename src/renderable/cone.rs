@@ -0,0 +1,165 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Interval, Aabb, Frame, swept};
+use renderable::disk::hit_ring;
+
+// Where a ray in local coordinates hits, as the distance along it, the local normal, and the surface coordinates.
+pub type SurfaceHit = (f64, Vector3, (f64, f64));
+
+// A cone, or a frustum when the top radius is above zero, running from the center of its base to the center of
+// its top. Partial sweeps leave the cut edges open.
+pub struct Cone
+{
+    pub frame: Frame,
+    pub height: f64,
+    pub base_radius: f64,
+    pub top_radius: f64,
+    // Whether the base and top are closed off by disks.
+    pub capped: bool,
+    // How far around the axis the cone reaches, in degrees.
+    pub sweep: f64,
+    pub material: Box<Material>
+}
+
+impl Cone
+{
+    pub fn new<T: Material + 'static>(base: Vector3, top: Vector3, base_radius: f64, top_radius: f64, capped: bool, material: T) -> Cone
+    {
+        Cone
+        {
+            frame: Frame::new(base, top - base),
+            height: (top - base).length(),
+            base_radius: base_radius,
+            top_radius: top_radius,
+            capped: capped,
+            sweep: 360.0,
+            material: Box::new(material)
+        }
+    }
+
+    fn surface(&self, ray: Ray, (t, normal, uv): SurfaceHit) -> HitResult
+    {
+        HitResult
+        {
+            origin: ray.translate_to(t),
+            normal: self.frame.world_vector(normal),
+            t: t,
+            time: ray.time,
+            uv: uv,
+            material: &*self.material
+        }
+    }
+}
+
+// The nearest hit of a ray in local coordinates on the side of a frustum standing on the origin along z, as the
+// distance along it, the local normal, and the coordinates around and up the side. Shared with cylinders.
+pub fn hit_side(ray: Ray, base_radius: f64, top_radius: f64, height: f64, sweep: f64, min_t: f64, max_t: f64) -> Option<SurfaceHit>
+{
+    // Points on the side are where x² + y² = r(z)², with the radius r changing by slope for each unit up.
+    let slope = (top_radius - base_radius) / height;
+    let (o, d) = (ray.origin, ray.direction);
+    let radius = base_radius + slope * o.z;
+    let a = d.x * d.x + d.y * d.y - slope * slope * d.z * d.z;
+    let b = o.x * d.x + o.y * d.y - slope * d.z * radius;
+    let c = o.x * o.x + o.y * o.y - radius * radius;
+
+    let roots = if a.abs() < 1e-12
+    {
+        // Rays parallel to the slant cross the side at most once, and rays along a cylinder not at all.
+        if b == 0.0 { return None; }
+        [-c / (2.0 * b), f64::NAN]
+    }
+    else
+    {
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 { return None; }
+        let (near, far) = ((-b - discriminant.sqrt()) / a, (-b + discriminant.sqrt()) / a);
+        [near.min(far), near.max(far)]
+    };
+
+    for &t in roots.iter()
+    {
+        if !(t > min_t && t < max_t)
+        {
+            continue;
+        }
+        let point = ray.translate_to(t);
+        if point.z < 0.0 || point.z > height
+        {
+            continue;
+        }
+        if let Some(u) = swept(point.x, point.y, sweep)
+        {
+            // The gradient of x² + y² - r(z)², which has no direction at the tip of a cone.
+            let gradient = Vector3::new(point.x, point.y, -slope * (base_radius + slope * point.z));
+            let normal = if gradient.length_sqr() > 0.0 { gradient.normalized() } else { Vector3::new(0.0, 0.0, slope.signum()) };
+            return Some((t, normal, (u, point.z / height)));
+        }
+    }
+
+    None
+}
+
+// The nearest hit on the side and caps of a frustum in local coordinates, given its base and top radii. Caps with no
+// radius are left out.
+pub fn hit_frustum(ray: Ray, (base_radius, top_radius): (f64, f64), height: f64, capped: bool, sweep: f64, min_t: f64, max_t: f64) -> Option<SurfaceHit>
+{
+    let mut nearest = hit_side(ray, base_radius, top_radius, height, sweep, min_t, max_t);
+    if capped
+    {
+        let caps = [(0.0, base_radius, Vector3::new(0.0, 0.0, -1.0)), (height, top_radius, Vector3::new(0.0, 0.0, 1.0))];
+        for &(z, radius, normal) in caps.iter().filter(|c| c.1 > 0.0)
+        {
+            let limit = nearest.map_or(max_t, |n| n.0);
+            if let Some((t, uv)) = hit_ring(ray, z, 0.0, radius, sweep, min_t, limit)
+            {
+                nearest = Some((t, normal, uv));
+            }
+        }
+    }
+    nearest
+}
+
+// Where a ray in local coordinates enters and leaves a closed frustum, which it crosses at most twice as it's convex.
+pub fn frustum_crossings(ray: Ray, radii: (f64, f64), height: f64) -> Option<(SurfaceHit, SurfaceHit)>
+{
+    let enter = hit_frustum(ray, radii, height, true, 360.0, -f64::MAX, f64::MAX)?;
+    let exit = hit_frustum(ray, radii, height, true, 360.0, enter.0, f64::MAX)?;
+    Some((enter, exit))
+}
+
+impl Renderable for Cone
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let local = self.frame.local_ray(ray);
+        hit_frustum(local, (self.base_radius, self.top_radius), self.height, self.capped, self.sweep, min_t, max_t).map(|hit| self.surface(ray, hit))
+    }
+
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        if !self.capped || self.sweep < 360.0
+        {
+            return None;
+        }
+        let crossings = frustum_crossings(self.frame.local_ray(ray), (self.base_radius, self.top_radius), self.height);
+        Some(crossings.map(|(enter, exit)| Interval { enter: self.surface(ray, enter), exit: self.surface(ray, exit) }).into_iter().collect())
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "cone"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let top = self.frame.origin + self.frame.axis * self.height;
+        Some(Aabb::around_circle(self.frame.origin, self.frame.axis, self.base_radius).union(&Aabb::around_circle(top, self.frame.axis, self.top_radius)))
+    }
+}
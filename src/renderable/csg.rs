@@ -0,0 +1,140 @@
+use ray::Ray;
+use material::Material;
+use vector3::{ZERO, Vector3};
+use renderable::{Renderable, HitResult, Interval, Aabb};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Operation
+{
+    Union,
+    Intersection,
+    // What's inside the first solid but not the second.
+    Difference
+}
+
+// Two closed solids combined into one. Each part of the surface keeps the material of the solid it came from, so
+// holes cut by a difference are lined with the material of the solid doing the cutting.
+pub struct Csg
+{
+    pub operation: Operation,
+    pub a: Box<Renderable>,
+    pub b: Box<Renderable>
+}
+
+impl Csg
+{
+    pub fn new<A: Renderable + 'static, B: Renderable + 'static>(operation: Operation, a: A, b: B) -> Csg
+    {
+        let probe = Ray { origin: ZERO, direction: Vector3::new(1.0, 0.0, 0.0), time: 0.0 };
+        assert!(a.intervals(probe).is_some() && b.intervals(probe).is_some(), "CSG needs closed solids, {} and {} aren't both", a.kind(), b.kind());
+        Csg { operation: operation, a: Box::new(a), b: Box::new(b) }
+    }
+
+    pub fn union<A: Renderable + 'static, B: Renderable + 'static>(a: A, b: B) -> Csg
+    {
+        Csg::new(Operation::Union, a, b)
+    }
+
+    pub fn intersection<A: Renderable + 'static, B: Renderable + 'static>(a: A, b: B) -> Csg
+    {
+        Csg::new(Operation::Intersection, a, b)
+    }
+
+    pub fn difference<A: Renderable + 'static, B: Renderable + 'static>(a: A, b: B) -> Csg
+    {
+        Csg::new(Operation::Difference, a, b)
+    }
+
+    fn inside(&self, in_a: bool, in_b: bool) -> bool
+    {
+        match self.operation
+        {
+            Operation::Union => in_a || in_b,
+            Operation::Intersection => in_a && in_b,
+            Operation::Difference => in_a && !in_b
+        }
+    }
+}
+
+impl Renderable for Csg
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        // Rays starting inside hit on their way out, as with any other solid.
+        self.intervals(ray).unwrap().into_iter()
+            .flat_map(|i| vec![i.enter, i.exit])
+            .find(|h| h.t > min_t && h.t < max_t)
+    }
+
+    // Walks through every boundary of either solid in order, keeping track of which solids the ray is in, and notes
+    // where that takes it in or out of the combination.
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        let mut boundaries: Vec<(HitResult, bool, bool)> = Vec::new();
+        for (solid, is_b) in [(&self.a, false), (&self.b, true)].iter()
+        {
+            for interval in solid.intervals(ray).unwrap()
+            {
+                boundaries.push((interval.enter, *is_b, true));
+                boundaries.push((interval.exit, *is_b, false));
+            }
+        }
+        boundaries.sort_by(|x, y| x.0.t.partial_cmp(&y.0.t).unwrap());
+
+        let (mut in_a, mut in_b) = (false, false);
+        let mut enter: Option<HitResult> = None;
+        let mut intervals = Vec::new();
+        for &(hit, is_b, entering) in boundaries.iter()
+        {
+            let was_inside = self.inside(in_a, in_b);
+            if is_b { in_b = entering } else { in_a = entering }
+            let inside = self.inside(in_a, in_b);
+            if inside == was_inside
+            {
+                continue;
+            }
+
+            // The second solid's surface faces the other way where it bounds a difference.
+            let mut hit = hit;
+            if is_b && self.operation == Operation::Difference
+            {
+                hit.normal = -hit.normal;
+            }
+            if inside
+            {
+                enter = Some(hit);
+            }
+            else if let Some(enter) = enter.take()
+            {
+                intervals.push(Interval { enter: enter, exit: hit });
+            }
+        }
+        Some(intervals)
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "csg"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        let mut materials = self.a.materials();
+        materials.extend(self.b.materials());
+        materials
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        match self.operation
+        {
+            Operation::Union => match (self.a.bounds(), self.b.bounds())
+            {
+                (Some(a), Some(b)) => Some(a.union(&b)),
+                _ => None
+            },
+            Operation::Intersection => self.a.bounds().or_else(|| self.b.bounds()),
+            Operation::Difference => self.a.bounds()
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use std::f64;
-use vector3::Vector3;
+use vector3::{ZERO, Vector3};
 use ray::Ray;
 use material::Material;
-use renderable::{Renderable, HitResult};
+use renderable::{Renderable, HitResult, Interval, Aabb};
 
 pub struct Cube
 {
@@ -10,6 +10,8 @@ pub struct Cube
     pub width: f64,
     pub height: f64,
     pub depth: f64,
+    // How far the cube moves in a unit of time, with origin being where it is at time zero.
+    pub velocity: Vector3,
     pub material: Box<Material>
 }
 
@@ -17,86 +19,136 @@ impl Cube
 {
     pub fn new<T: Material + 'static>(origin: Vector3, width: f64, height: f64, depth: f64, material: T) -> Cube
     {
-        Cube { origin: origin, width: width, height: height, depth: depth, material: Box::new(material) }
+        Cube { origin: origin, width: width, height: height, depth: depth, velocity: ZERO, material: Box::new(material) }
     }
-}
 
-impl Renderable for Cube
-{
-    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    // A cube moving in a straight line from start at time zero to end at time one, and on along the same line at the
+    // same speed at any other time.
+    pub fn moving<T: Material + 'static>(start: Vector3, end: Vector3, width: f64, height: f64, depth: f64, material: T) -> Cube
+    {
+        Cube { origin: start, width: width, height: height, depth: depth, velocity: end - start, material: Box::new(material) }
+    }
+
+    // The times the ray enters and leaves the cube, found by clipping it against each pair of faces.
+    fn crossings(&self, ray: Ray) -> Option<(f64, f64)>
     {
         let half_width = self.width / 2.0;
         let half_height = self.height / 2.0;
         let half_depth = self.depth / 2.0;
+        let origin = self.origin + self.velocity * ray.time;
         let mut t_min = -f64::INFINITY;
         let mut t_max = f64::INFINITY;
 
         if ray.direction.x != 0.0
         {
-            let tx0 = (self.origin.x - half_width - ray.origin.x) / ray.direction.x;
-            let tx1 = (self.origin.x + half_width - ray.origin.x) / ray.direction.x;
+            let tx0 = (origin.x - half_width - ray.origin.x) / ray.direction.x;
+            let tx1 = (origin.x + half_width - ray.origin.x) / ray.direction.x;
 
             t_min = t_min.max(tx0.min(tx1));
             t_max = t_max.min(tx0.max(tx1));
         }
+        else if (ray.origin.x - origin.x).abs() > half_width
+        {
+            return None;
+        }
 
         if ray.direction.y != 0.0
         {
-            let tx0 = (self.origin.y - half_height - ray.origin.y) / ray.direction.y;
-            let tx1 = (self.origin.y + half_height - ray.origin.y) / ray.direction.y;
+            let tx0 = (origin.y - half_height - ray.origin.y) / ray.direction.y;
+            let tx1 = (origin.y + half_height - ray.origin.y) / ray.direction.y;
 
             t_min = t_min.max(tx0.min(tx1));
             t_max = t_max.min(tx0.max(tx1));
         }
+        else if (ray.origin.y - origin.y).abs() > half_height
+        {
+            return None;
+        }
 
         if ray.direction.z != 0.0
         {
-            let tx0 = (self.origin.z - half_depth - ray.origin.z) / ray.direction.z;
-            let tx1 = (self.origin.z + half_depth - ray.origin.z) / ray.direction.z;
+            let tx0 = (origin.z - half_depth - ray.origin.z) / ray.direction.z;
+            let tx1 = (origin.z + half_depth - ray.origin.z) / ray.direction.z;
 
             t_min = t_min.max(tx0.min(tx1));
             t_max = t_max.min(tx0.max(tx1));
         }
+        else if (ray.origin.z - origin.z).abs() > half_depth
+        {
+            return None;
+        }
+
+        if t_max < t_min
+        {
+            return None;
+        }
+
+        Some((t_min, t_max))
+    }
+
+    fn surface(&self, ray: Ray, t: f64) -> HitResult
+    {
+        let (half_width, half_height, half_depth) = (self.width / 2.0, self.height / 2.0, self.depth / 2.0);
+        let origin = self.origin + self.velocity * ray.time;
+        let point = ray.translate_to(t);
+        let local = point - origin;
+        let scaled = Vector3{x: local.x / half_width, y: local.y / half_height, z: local.z / half_depth};
+
+        // The face hit is the one the point lies furthest towards, relative to the size of the cube, and its
+        // coordinates are the other two axes in order.
+        let (normal, uv) = if scaled.x.abs() >= scaled.y.abs() && scaled.x.abs() >= scaled.z.abs()
+        {
+            (Vector3{x: scaled.x.signum(), y: 0.0, z: 0.0}, (scaled.y, scaled.z))
+        }
+        else if scaled.y.abs() >= scaled.z.abs()
+        {
+            (Vector3{x: 0.0, y: scaled.y.signum(), z: 0.0}, (scaled.x, scaled.z))
+        }
+        else
+        {
+            (Vector3{x: 0.0, y: 0.0, z: scaled.z.signum()}, (scaled.x, scaled.y))
+        };
 
-        if t_min >= min_t && t_max <= max_t && t_max >= t_min
+        HitResult
         {
-            let point = ray.translate_to(t_min);
-            let mut normal = point - self.origin;
-
-            if normal.x == self.origin.x - half_width
-            {
-                normal = Vector3{x: -1.0, y: 0.0, z: 0.0};
-            }
-            else if normal.x == self.origin.x + half_width
-            {
-                normal = Vector3{x: 1.0, y: 0.0, z: 0.0};
-            }
-            else if normal.y == self.origin.x - half_height
-            {
-                normal = Vector3{x: 0.0, y: -1.0, z: 0.0};
-            }
-            else if normal.y == self.origin.x + half_height
-            {
-                normal = Vector3{x: 0.0, y: 1.0, z: 0.0};
-            }
-            else if normal.z == self.origin.x - half_depth
-            {
-                normal = Vector3{x: 0.0, y: 0.0, z: -1.0};
-            }
-            else if normal.z == self.origin.x + half_depth
-            {
-                normal = Vector3{x: 0.0, y: 0.0, z: 1.0};
-            }
-
-            return Some(HitResult
-            {
-                origin: point, //self.origin + t_min * ray.direction,
-                normal: normal,
-                t: t_min,
-                material: &*self.material
-            });
+            origin: point,
+            normal: normal,
+            t: t,
+            time: ray.time,
+            uv: ((uv.0 + 1.0) / 2.0, (uv.1 + 1.0) / 2.0),
+            material: &*self.material
         }
+    }
+}
+
+impl Renderable for Cube
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let (t_min, t_max) = self.crossings(ray)?;
+        // Rays starting inside the cube hit it on their way out.
+        let t = if t_min > min_t { t_min } else { t_max };
+        if t > min_t && t < max_t { Some(self.surface(ray, t)) } else { None }
+    }
+
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        Some(self.crossings(ray).map(|(near, far)| Interval { enter: self.surface(ray, near), exit: self.surface(ray, far) }).into_iter().collect())
+    }
 
-        None
+    fn kind(&self) -> &'static str
+    {
+        "cube"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let half = Vector3::new(self.width, self.height, self.depth) / 2.0;
+        if self.velocity.length_sqr() == 0.0 { Some(Aabb::new(self.origin - half, self.origin + half)) } else { None }
     }
 }
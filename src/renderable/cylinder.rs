@@ -0,0 +1,82 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Interval, Aabb, Frame};
+use renderable::cone::{SurfaceHit, hit_frustum, frustum_crossings};
+
+// A cylinder running from the center of its base to the center of its top. Partial sweeps leave the cut edges open.
+pub struct Cylinder
+{
+    pub frame: Frame,
+    pub height: f64,
+    pub radius: f64,
+    // Whether the ends are closed off by disks.
+    pub capped: bool,
+    // How far around the axis the cylinder reaches, in degrees.
+    pub sweep: f64,
+    pub material: Box<Material>
+}
+
+impl Cylinder
+{
+    pub fn new<T: Material + 'static>(base: Vector3, top: Vector3, radius: f64, capped: bool, material: T) -> Cylinder
+    {
+        Cylinder
+        {
+            frame: Frame::new(base, top - base),
+            height: (top - base).length(),
+            radius: radius,
+            capped: capped,
+            sweep: 360.0,
+            material: Box::new(material)
+        }
+    }
+
+    fn surface(&self, ray: Ray, (t, normal, uv): SurfaceHit) -> HitResult
+    {
+        HitResult
+        {
+            origin: ray.translate_to(t),
+            normal: self.frame.world_vector(normal),
+            t: t,
+            time: ray.time,
+            uv: uv,
+            material: &*self.material
+        }
+    }
+}
+
+impl Renderable for Cylinder
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let local = self.frame.local_ray(ray);
+        hit_frustum(local, (self.radius, self.radius), self.height, self.capped, self.sweep, min_t, max_t).map(|hit| self.surface(ray, hit))
+    }
+
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        if !self.capped || self.sweep < 360.0
+        {
+            return None;
+        }
+        let crossings = frustum_crossings(self.frame.local_ray(ray), (self.radius, self.radius), self.height);
+        Some(crossings.map(|(enter, exit)| Interval { enter: self.surface(ray, enter), exit: self.surface(ray, exit) }).into_iter().collect())
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "cylinder"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let top = self.frame.origin + self.frame.axis * self.height;
+        Some(Aabb::around_circle(self.frame.origin, self.frame.axis, self.radius).union(&Aabb::around_circle(top, self.frame.axis, self.radius)))
+    }
+}
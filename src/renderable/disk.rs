@@ -0,0 +1,82 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Aabb, Frame, swept};
+
+// A flat disk, or an annulus when the inner radius is above zero, facing along its normal.
+pub struct Disk
+{
+    pub frame: Frame,
+    pub radius: f64,
+    pub inner_radius: f64,
+    // How far around the normal the disk reaches from its tangent, in degrees.
+    pub sweep: f64,
+    pub material: Box<Material>
+}
+
+impl Disk
+{
+    pub fn new<T: Material + 'static>(center: Vector3, normal: Vector3, radius: f64, material: T) -> Disk
+    {
+        Disk::annulus(center, normal, 0.0, radius, material)
+    }
+
+    pub fn annulus<T: Material + 'static>(center: Vector3, normal: Vector3, inner_radius: f64, radius: f64, material: T) -> Disk
+    {
+        Disk { frame: Frame::new(center, normal), radius: radius, inner_radius: inner_radius, sweep: 360.0, material: Box::new(material) }
+    }
+}
+
+// Where a ray in local coordinates crosses the ring at height z between the two radii, as the distance along it and
+// the coordinates around and out across the ring. Shared with the caps of cylinders and cones.
+pub fn hit_ring(ray: Ray, z: f64, inner_radius: f64, radius: f64, sweep: f64, min_t: f64, max_t: f64) -> Option<(f64, (f64, f64))>
+{
+    if ray.direction.z == 0.0
+    {
+        return None;
+    }
+    let t = (z - ray.origin.z) / ray.direction.z;
+    if t <= min_t || t >= max_t
+    {
+        return None;
+    }
+
+    let point = ray.translate_to(t);
+    let distance = (point.x * point.x + point.y * point.y).sqrt();
+    if distance < inner_radius || distance > radius
+    {
+        return None;
+    }
+    swept(point.x, point.y, sweep).map(|u| (t, (u, (distance - inner_radius) / (radius - inner_radius))))
+}
+
+impl Renderable for Disk
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        hit_ring(self.frame.local_ray(ray), 0.0, self.inner_radius, self.radius, self.sweep, min_t, max_t).map(|(t, uv)| HitResult
+        {
+            origin: ray.translate_to(t),
+            normal: self.frame.axis,
+            t: t,
+            time: ray.time,
+            uv: uv,
+            material: &*self.material
+        })
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "disk"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        Some(Aabb::around_circle(self.frame.origin, self.frame.axis, self.radius))
+    }
+}
@@ -0,0 +1,263 @@
+use lodepng;
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Aabb};
+
+// Terrain from a grid of heights, spread evenly over a rectangle on the xz plane. Each cell between four samples is
+// split into two triangles along its diagonal, which are only ever intersected where rays pass over them, found by
+// walking down blocks of cells that know their lowest and highest heights.
+pub struct Heightfield
+{
+    // The corner of the first sample at height zero, and how far the field reaches along x, up and along z.
+    pub origin: Vector3,
+    pub size: Vector3,
+    pub material: Box<Material>,
+    // Samples from zero to one, a row of them along x for each step along z.
+    columns: usize,
+    rows: usize,
+    heights: Vec<f64>,
+    // The heights each block of cells lies between, with the cells themselves first, and each level after taking
+    // blocks of two by two from the one before, until one block holds everything.
+    levels: Vec<Level>
+}
+
+struct Level
+{
+    columns: usize,
+    rows: usize,
+    ranges: Vec<(f64, f64)>
+}
+
+impl Heightfield
+{
+    pub fn new<T: Material + 'static>(columns: usize, rows: usize, heights: Vec<f64>, origin: Vector3, size: Vector3, material: T) -> Heightfield
+    {
+        assert!(columns >= 2 && rows >= 2 && heights.len() == columns * rows, "A heightfield needs at least two by two samples, and one height for each");
+        assert!(size.x > 0.0 && size.y > 0.0 && size.z > 0.0, "A heightfield needs some size along every axis");
+
+        let mut cells = Level { columns: columns - 1, rows: rows - 1, ranges: Vec::with_capacity((columns - 1) * (rows - 1)) };
+        for j in 0..rows - 1
+        {
+            for i in 0..columns - 1
+            {
+                let corners = [heights[j * columns + i], heights[j * columns + i + 1], heights[(j + 1) * columns + i], heights[(j + 1) * columns + i + 1]];
+                cells.ranges.push((corners.iter().cloned().fold(f64::MAX, f64::min), corners.iter().cloned().fold(f64::MIN, f64::max)));
+            }
+        }
+
+        let mut levels = vec![cells];
+        loop
+        {
+            let below = levels.last().unwrap();
+            if below.columns == 1 && below.rows == 1
+            {
+                break;
+            }
+            let (columns, rows) = (below.columns.div_ceil(2), below.rows.div_ceil(2));
+            let mut ranges = vec![(f64::MAX, f64::MIN); columns * rows];
+            for j in 0..below.rows
+            {
+                for i in 0..below.columns
+                {
+                    let (low, high) = below.ranges[j * below.columns + i];
+                    let range = &mut ranges[(j / 2) * columns + i / 2];
+                    *range = (range.0.min(low), range.1.max(high));
+                }
+            }
+            levels.push(Level { columns: columns, rows: rows, ranges: ranges });
+        }
+
+        Heightfield
+        {
+            origin: origin,
+            size: size,
+            material: Box::new(material),
+            columns: columns,
+            rows: rows,
+            heights: heights,
+            levels: levels
+        }
+    }
+
+    // Takes heights from how bright each pixel of an image is, with its top row at the origin's z.
+    pub fn load<T: Material + 'static>(path: &str, origin: Vector3, size: Vector3, material: T) -> Result<Heightfield, String>
+    {
+        // Sixteen bits a channel keeps all the precision of 16-bit height maps, and 8-bit ones are scaled up to it.
+        let image = match lodepng::decode_file(path, lodepng::ColorType::RGBA, 16).map_err(|e| format!("Couldn't load height map {}: {}", path, e))?
+        {
+            lodepng::Image::RGBA16(image) => image,
+            _ => unreachable!()
+        };
+        if image.width < 2 || image.height < 2
+        {
+            return Err(format!("Height map {} needs at least two pixels each way", path));
+        }
+
+        // The samples are left in the big-endian order they're stored in.
+        let heights = image.buffer.iter()
+            .map(|p| (u16::from_be(p.r) as f64 + u16::from_be(p.g) as f64 + u16::from_be(p.b) as f64) / (3.0 * 65535.0))
+            .collect();
+        Ok(Heightfield::new(image.width, image.height, heights, origin, size, material))
+    }
+
+    fn height(&self, i: usize, j: usize) -> f64
+    {
+        self.heights[j * self.columns + i]
+    }
+
+    fn spacing(&self) -> (f64, f64)
+    {
+        (self.size.x / (self.columns - 1) as f64, self.size.z / (self.rows - 1) as f64)
+    }
+
+    // Rays in the grid's own space, where samples are a unit apart and heights run from zero to one.
+    fn grid_ray(&self, ray: Ray) -> Ray
+    {
+        let (dx, dz) = self.spacing();
+        let scale = |v: Vector3| Vector3::new(v.x / dx, v.y / self.size.y, v.z / dz);
+        Ray { origin: scale(ray.origin - self.origin), direction: scale(ray.direction), time: ray.time }
+    }
+
+    // The normal of the smooth surface through the samples, from the slopes to the samples on either side.
+    fn sample_normal(&self, i: usize, j: usize) -> Vector3
+    {
+        let (dx, dz) = self.spacing();
+        let (left, right) = (i.saturating_sub(1), (i + 1).min(self.columns - 1));
+        let (back, front) = (j.saturating_sub(1), (j + 1).min(self.rows - 1));
+        let slope_x = (self.height(right, j) - self.height(left, j)) * self.size.y / ((right - left) as f64 * dx);
+        let slope_z = (self.height(i, front) - self.height(i, back)) * self.size.y / ((front - back) as f64 * dz);
+        Vector3::new(-slope_x, 1.0, -slope_z).normalized()
+    }
+
+    // The nearest hit on the two triangles of a cell, as the distance along the ray and the normal there, blended
+    // between the normals at the triangle's corners.
+    fn hit_cell(&self, ray: Ray, i: usize, j: usize, min_t: f64, max_t: f64) -> Option<(f64, Vector3)>
+    {
+        let (h00, h10, h01, h11) = (self.height(i, j), self.height(i + 1, j), self.height(i, j + 1), self.height(i + 1, j + 1));
+        let (x, z) = (ray.origin.x - i as f64, ray.origin.z - j as f64);
+        let d = ray.direction;
+
+        // Each triangle is the plane height = h00 + a x + b z over its half of the cell, on one side of the diagonal.
+        let mut nearest: Option<(f64, Vector3)> = None;
+        for &below_diagonal in [true, false].iter()
+        {
+            let (a, b) = if below_diagonal { (h10 - h00, h11 - h10) } else { (h11 - h01, h01 - h00) };
+            let denominator = d.y - a * d.x - b * d.z;
+            if denominator == 0.0
+            {
+                continue;
+            }
+            let t = (h00 + a * x + b * z - ray.origin.y) / denominator;
+            if t <= min_t || t >= nearest.map_or(max_t, |n| n.0)
+            {
+                continue;
+            }
+            let (u, v) = (x + t * d.x, z + t * d.z);
+            let other_side = if below_diagonal { v > u } else { u > v };
+            if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) || other_side
+            {
+                continue;
+            }
+
+            let normal = if below_diagonal
+            {
+                self.sample_normal(i, j) * (1.0 - u) + self.sample_normal(i + 1, j) * (u - v) + self.sample_normal(i + 1, j + 1) * v
+            }
+            else
+            {
+                self.sample_normal(i, j) * (1.0 - v) + self.sample_normal(i + 1, j + 1) * u + self.sample_normal(i, j + 1) * (v - u)
+            };
+            nearest = Some((t, normal.normalized()));
+        }
+        nearest
+    }
+
+    // The box around a block of cells in grid space.
+    fn block(&self, level: usize, a: usize, b: usize) -> Aabb
+    {
+        let (cells, span) = (&self.levels[0], 1 << level);
+        let (low, high) = self.levels[level].ranges[b * self.levels[level].columns + a];
+        Aabb::new(Vector3::new((a * span) as f64, low, (b * span) as f64),
+            Vector3::new((((a + 1) * span).min(cells.columns)) as f64, high, (((b + 1) * span).min(cells.rows)) as f64))
+    }
+
+    // Looks through the parts of a block the ray passes over nearest first, stopping at the first that has a hit.
+    fn hit_block(&self, ray: Ray, level: usize, a: usize, b: usize, min_t: f64, max_t: f64) -> Option<(f64, Vector3)>
+    {
+        if level == 0
+        {
+            return self.hit_cell(ray, a, b, min_t, max_t);
+        }
+
+        let below = &self.levels[level - 1];
+        let mut parts = [(0.0, 0, 0); 4];
+        let mut count = 0;
+        for &(i, j) in [(2 * a, 2 * b), (2 * a + 1, 2 * b), (2 * a, 2 * b + 1), (2 * a + 1, 2 * b + 1)].iter()
+        {
+            if i < below.columns && j < below.rows
+            {
+                if let Some((enter, _)) = self.block(level - 1, i, j).clip(ray, min_t, max_t)
+                {
+                    parts[count] = (enter, i, j);
+                    count += 1;
+                }
+            }
+        }
+        parts[..count].sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+
+        let mut nearest: Option<(f64, Vector3)> = None;
+        for &(enter, i, j) in parts[..count].iter()
+        {
+            let limit = nearest.map_or(max_t, |n| n.0);
+            if enter >= limit
+            {
+                break;
+            }
+            if let Some(hit) = self.hit_block(ray, level - 1, i, j, min_t, limit)
+            {
+                nearest = Some(hit);
+            }
+        }
+        nearest
+    }
+}
+
+impl Renderable for Heightfield
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let grid = self.grid_ray(ray);
+        let top = self.levels.len() - 1;
+        self.block(top, 0, 0).clip(grid, min_t, max_t)?;
+        self.hit_block(grid, top, 0, 0, min_t, max_t).map(|(t, normal)|
+        {
+            let point = grid.translate_to(t);
+            HitResult
+            {
+                origin: ray.translate_to(t),
+                normal: normal,
+                t: t,
+                time: ray.time,
+                uv: (point.x / (self.columns - 1) as f64, point.z / (self.rows - 1) as f64),
+                material: &*self.material
+            }
+        })
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "heightfield"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let (low, high) = self.levels[self.levels.len() - 1].ranges[0];
+        Some(Aabb::new(self.origin + Vector3::new(0.0, low * self.size.y, 0.0), self.origin + Vector3::new(self.size.x, high * self.size.y, self.size.z)))
+    }
+}
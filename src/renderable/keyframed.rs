@@ -0,0 +1,66 @@
+use animation::Track;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Interval};
+use transform::Transform;
+
+// Moves any renderable through the transforms of an animation track, by the time of each ray.
+pub struct Keyframed
+{
+    pub renderable: Box<Renderable>,
+    pub track: Track<Transform>
+}
+
+impl Keyframed
+{
+    pub fn new<T: Renderable + 'static>(renderable: T, track: Track<Transform>) -> Keyframed
+    {
+        Keyframed { renderable: Box::new(renderable), track: track }
+    }
+}
+
+impl Renderable for Keyframed
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let transform = self.track.value_at(ray.time);
+        self.renderable.test_hit(local_ray(ray, &transform), min_t, max_t).map(|h| world_hit(ray, &transform, h))
+    }
+
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        let transform = self.track.value_at(ray.time);
+        self.renderable.intervals(local_ray(ray, &transform)).map(|intervals| intervals.into_iter()
+            .map(|i| Interval { enter: world_hit(ray, &transform, i.enter), exit: world_hit(ray, &transform, i.exit) })
+            .collect())
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        self.renderable.kind()
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        self.renderable.materials()
+    }
+}
+
+// Without normalizing the direction, distances along the ray are the same on either side of the transform.
+fn local_ray(ray: Ray, transform: &Transform) -> Ray
+{
+    Ray { origin: transform.inverse_point(ray.origin), direction: transform.inverse_vector(ray.direction), time: ray.time }
+}
+
+fn world_hit<'a>(ray: Ray, transform: &Transform, h: HitResult<'a>) -> HitResult<'a>
+{
+    HitResult
+    {
+        origin: ray.translate_to(h.t),
+        normal: transform.rotate(h.normal),
+        t: h.t,
+        time: h.time,
+        uv: h.uv,
+        material: h.material
+    }
+}
@@ -2,7 +2,20 @@ pub mod plane;
 pub mod plane_bounded;
 pub mod sphere;
 pub mod cube;
+pub mod triangle;
+pub mod keyframed;
+pub mod cylinder;
+pub mod cone;
+pub mod disk;
+pub mod torus;
+pub mod quartic;
+pub mod csg;
+pub mod sdf;
+pub mod heightfield;
+#[cfg(test)]
+mod tests;
 
+use std::f64::consts::PI;
 use vector3::Vector3;
 use ray::Ray;
 use material::Material;
@@ -15,10 +28,174 @@ pub struct HitResult<'a>
     pub origin: Vector3,
     pub normal: Vector3,
     pub t: f64,
+    // The time of the ray that hit.
+    pub time: f64,
+    // Where the hit is in the surface's own coordinates, each running from zero to one across it.
+    pub uv: (f64, f64),
     pub material: &'a Material
 }
 
-pub trait Renderable
+pub trait Renderable: Sync
 {
     fn test_hit(&self, ray: Ray, min_time: f64, max_time: f64) -> Option<HitResult>;
+    // A name for the type of renderable, which render statistics are grouped by.
+    fn kind(&self) -> &'static str;
+    // Every material the renderable's surfaces use, which the scene numbers for the material ID AOV.
+    fn materials(&self) -> Vec<&Material>;
+    // Every stretch of the whole line along the ray that lies inside the renderable, in order, or None if it isn't a
+    // closed solid with an inside to speak of.
+    #[allow(unused_variables)]
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        None
+    }
+    // A box the renderable lies within at any time, or None if it has no bounds or moves.
+    fn bounds(&self) -> Option<Aabb>
+    {
+        None
+    }
+}
+
+// Where a ray enters a solid and where it leaves again. Both normals point out of the solid.
+#[derive(Clone, Copy)]
+pub struct Interval<'a>
+{
+    pub enter: HitResult<'a>,
+    pub exit: HitResult<'a>
+}
+
+// An axis aligned bounding box.
+#[derive(Clone, Copy)]
+pub struct Aabb
+{
+    pub min: Vector3,
+    pub max: Vector3
+}
+
+impl Aabb
+{
+    pub fn new(min: Vector3, max: Vector3) -> Aabb
+    {
+        Aabb { min: min, max: max }
+    }
+
+    pub fn around(points: &[Vector3]) -> Aabb
+    {
+        points.iter().fold(Aabb::new(points[0], points[0]), |b, &p| b.union(&Aabb::new(p, p)))
+    }
+
+    // The box around a circle, which reaches radius times the sine of the angle between the normal and each axis.
+    pub fn around_circle(center: Vector3, normal: Vector3, radius: f64) -> Aabb
+    {
+        let normal = normal.normalized();
+        let reach = |a: f64| radius * (1.0 - a * a).max(0.0).sqrt();
+        let extent = Vector3::new(reach(normal.x), reach(normal.y), reach(normal.z));
+        Aabb::new(center - extent, center + extent)
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb
+    {
+        Aabb
+        {
+            min: Vector3::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            max: Vector3::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z))
+        }
+    }
+
+    pub fn padded(&self, margin: f64) -> Aabb
+    {
+        let margin = Vector3::new(margin, margin, margin);
+        Aabb::new(self.min - margin, self.max + margin)
+    }
+
+    pub fn hit(&self, ray: Ray, min_t: f64, max_t: f64) -> bool
+    {
+        self.clip(ray, min_t, max_t).is_some()
+    }
+
+    // The stretch of time between the two given that the ray spends inside the box, found by clipping it against
+    // each pair of sides.
+    pub fn clip(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<(f64, f64)>
+    {
+        let (mut near, mut far) = (min_t, max_t);
+        let axes = [(ray.origin.x, ray.direction.x, self.min.x, self.max.x), (ray.origin.y, ray.direction.y, self.min.y, self.max.y),
+            (ray.origin.z, ray.direction.z, self.min.z, self.max.z)];
+        for &(origin, direction, low, high) in axes.iter()
+        {
+            if direction == 0.0
+            {
+                if origin < low || origin > high
+                {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = ((low - origin) / direction, (high - origin) / direction);
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+            if near > far
+            {
+                return None;
+            }
+        }
+        Some((near, far))
+    }
+}
+
+// Coordinates around an axis, for shapes that are simplest to intersect in their own space. The tangent, bitangent
+// and axis are the local x, y and z, and angles around the axis are measured from the tangent towards the bitangent.
+#[derive(Clone, Copy)]
+pub struct Frame
+{
+    pub origin: Vector3,
+    pub tangent: Vector3,
+    pub bitangent: Vector3,
+    pub axis: Vector3
+}
+
+impl Frame
+{
+    pub fn new(origin: Vector3, axis: Vector3) -> Frame
+    {
+        let axis = axis.normalized();
+        let reference = if axis.z.abs() > 0.999 { Vector3::new(0.0, 1.0, 0.0) } else { Vector3::new(0.0, 0.0, 1.0) };
+        let tangent = axis.cross(reference).normalized();
+        Frame { origin: origin, tangent: tangent, bitangent: axis.cross(tangent), axis: axis }
+    }
+
+    // The ray in local coordinates. As the frame isn't scaled, distances along it are unchanged.
+    pub fn local_ray(&self, ray: Ray) -> Ray
+    {
+        let local = |v: Vector3| Vector3::new(v.dot(self.tangent), v.dot(self.bitangent), v.dot(self.axis));
+        Ray { origin: local(ray.origin - self.origin), direction: local(ray.direction), time: ray.time }
+    }
+
+    pub fn world_vector(&self, v: Vector3) -> Vector3
+    {
+        self.tangent * v.x + self.bitangent * v.y + self.axis * v.z
+    }
+}
+
+// How far around the sweep a local point is, from zero to one, or None if it's outside the sweep. The sweep is in
+// degrees, and a full turn or more never cuts anything away.
+pub fn swept(x: f64, y: f64, sweep: f64) -> Option<f64>
+{
+    let mut angle = y.atan2(x);
+    if angle < 0.0
+    {
+        angle += 2.0 * PI;
+    }
+    let sweep = sweep.to_radians();
+    if sweep >= 2.0 * PI
+    {
+        Some(angle / (2.0 * PI))
+    }
+    else if angle <= sweep
+    {
+        Some(angle / sweep)
+    }
+    else
+    {
+        None
+    }
 }
\ No newline at end of file
@@ -14,7 +14,7 @@ impl Plane
 {
     pub fn new<T: Material + 'static>(origin: Vector3, normal: Vector3, material: T) -> Plane
     {
-        Plane { origin: origin, normal: normal, material: Box::new(material) }
+        Plane { origin: origin, normal: normal.normalized(), material: Box::new(material) }
     }
 }
 
@@ -35,6 +35,9 @@ impl Renderable for Plane
                     origin: point,
                     normal: normal,
                     t: t,
+                    time: ray.time,
+                    // An infinite plane has no extent to measure coordinates across.
+                    uv: (0.0, 0.0),
                     material: &*self.material
                 });
             }
@@ -42,4 +45,14 @@ impl Renderable for Plane
 
         None
     }
+
+    fn kind(&self) -> &'static str
+    {
+        "plane"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
 use vector3::Vector3;
 use ray::Ray;
 use material::Material;
-use renderable::{Renderable, HitResult, EPSILON};
+use renderable::{Renderable, HitResult, Aabb, EPSILON};
 
 pub struct PlaneBounded
 {
     pub origin: Vector3,
     pub normal: Vector3,
+    pub tangent: Vector3,
+    pub bitangent: Vector3,
     pub width: f64,
     pub depth: f64,
     pub material: Box<Material>
@@ -14,9 +16,23 @@ pub struct PlaneBounded
 
 impl PlaneBounded
 {
+    // Width runs along the tangent and depth along the bitangent, which are the x and z axes for a plane facing up.
     pub fn new<T: Material + 'static>(origin: Vector3, normal: Vector3, width: f64, depth: f64, material: T) -> PlaneBounded
     {
-        PlaneBounded { origin: origin, normal: normal, width: width, depth: depth, material: Box::new(material) }
+        let normal = normal.normalized();
+        let reference = if normal.z.abs() > 0.999 { Vector3::new(0.0, 1.0, 0.0) } else { Vector3::new(0.0, 0.0, 1.0) };
+        let tangent = normal.cross(reference).normalized();
+        let bitangent = tangent.cross(normal);
+        PlaneBounded
+        {
+            origin: origin,
+            normal: normal,
+            tangent: tangent,
+            bitangent: bitangent,
+            width: width,
+            depth: depth,
+            material: Box::new(material)
+        }
     }
 }
 
@@ -33,13 +49,15 @@ impl Renderable for PlaneBounded
             {
                 let point = ray.translate_to(t);
                 let plane_point = point - self.origin;
-                if plane_point.x.abs() <= (self.width / 2.0) && plane_point.z.abs() <= (self.depth / 2.0)
+                if plane_point.dot(self.tangent).abs() <= (self.width / 2.0) && plane_point.dot(self.bitangent).abs() <= (self.depth / 2.0)
                 {
                     return Some(HitResult
                     {
                         origin: point,
                         normal: normal,
                         t: t,
+                        time: ray.time,
+                        uv: (plane_point.dot(self.tangent) / self.width + 0.5, plane_point.dot(self.bitangent) / self.depth + 0.5),
                         material: &*self.material
                     });
                 }
@@ -48,4 +66,20 @@ impl Renderable for PlaneBounded
 
         None
     }
+
+    fn kind(&self) -> &'static str
+    {
+        "plane_bounded"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let (tangent, bitangent) = (self.tangent * (self.width / 2.0), self.bitangent * (self.depth / 2.0));
+        Some(Aabb::around(&[self.origin - tangent - bitangent, self.origin - tangent + bitangent, self.origin + tangent - bitangent, self.origin + tangent + bitangent]))
+    }
 }
\ No newline at end of file
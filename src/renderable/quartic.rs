@@ -0,0 +1,122 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use polynomial;
+use renderable::{Renderable, HitResult, Aabb};
+
+// A term of an implicit surface's polynomial, with the powers x, y and z are raised to.
+#[derive(Clone, Copy)]
+pub struct Term
+{
+    pub coefficient: f64,
+    pub powers: [u32; 3]
+}
+
+impl Term
+{
+    pub fn new(coefficient: f64, x: u32, y: u32, z: u32) -> Term
+    {
+        Term { coefficient: coefficient, powers: [x, y, z] }
+    }
+}
+
+// The surface where a polynomial in x, y and z of degree up to four is zero, cut off at the bounds, as implicit
+// surfaces can run off to infinity. The polynomial should be negative inside, which the normals point away from.
+pub struct Quartic
+{
+    pub terms: Vec<Term>,
+    pub bounds: Aabb,
+    pub material: Box<Material>
+}
+
+impl Quartic
+{
+    pub fn new<T: Material + 'static>(terms: Vec<Term>, bounds: Aabb, material: T) -> Quartic
+    {
+        assert!(terms.iter().all(|t| t.powers.iter().sum::<u32>() <= 4), "Quartic surfaces can only have terms up to the fourth power");
+        Quartic { terms: terms, bounds: bounds, material: Box::new(material) }
+    }
+
+    pub fn gradient(&self, p: Vector3) -> Vector3
+    {
+        let coordinates = [p.x, p.y, p.z];
+        let mut gradient = [0.0; 3];
+        for term in self.terms.iter()
+        {
+            for (axis, slope) in gradient.iter_mut().enumerate()
+            {
+                if term.powers[axis] == 0
+                {
+                    continue;
+                }
+                let mut value = term.coefficient * term.powers[axis] as f64;
+                for (other, &c) in coordinates.iter().enumerate()
+                {
+                    let power = if other == axis { term.powers[other] - 1 } else { term.powers[other] };
+                    value *= c.powi(power as i32);
+                }
+                *slope += value;
+            }
+        }
+        Vector3::new(gradient[0], gradient[1], gradient[2])
+    }
+}
+
+impl Renderable for Quartic
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let (near, far) = self.bounds.clip(ray, min_t, max_t)?;
+
+        // The polynomial along the ray, measured from where it enters the bounds to keep the coefficients small.
+        let start = ray.translate_to(near);
+        let lines = [[start.x, ray.direction.x], [start.y, ray.direction.y], [start.z, ray.direction.z]];
+        let mut coefficients = vec![0.0; 5];
+        for term in self.terms.iter()
+        {
+            let mut product = vec![term.coefficient];
+            for (axis, line) in lines.iter().enumerate()
+            {
+                for _ in 0..term.powers[axis]
+                {
+                    product = polynomial::multiply(&product, line);
+                }
+            }
+            for (c, p) in coefficients.iter_mut().zip(product.iter())
+            {
+                *c += p;
+            }
+        }
+
+        let roots = polynomial::roots(&coefficients, 0.0, far - near);
+        let t = roots.iter().map(|s| s + near).find(|&t| t > min_t && t < max_t)?;
+        let point = ray.translate_to(t);
+        let gradient = self.gradient(point);
+        let size = self.bounds.max - self.bounds.min;
+        Some(HitResult
+        {
+            origin: point,
+            normal: if gradient.length_sqr() > 0.0 { gradient.normalized() } else { -ray.direction.normalized() },
+            t: t,
+            time: ray.time,
+            // Projected straight down onto the bounds.
+            uv: ((point.x - self.bounds.min.x) / size.x, (point.z - self.bounds.min.z) / size.z),
+            material: &*self.material
+        })
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "quartic"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        Some(self.bounds)
+    }
+}
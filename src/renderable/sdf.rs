@@ -0,0 +1,143 @@
+use distance::{self, DistanceField};
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Aabb};
+
+// A shape given by a signed distance field, found by sphere tracing: stepping along the ray by the distance to the
+// nearest surface, which can never step through one, until close enough to count as a hit.
+pub struct Sdf
+{
+    pub field: Box<DistanceField>,
+    // Where the field has anything, which rays are clipped to. Without bounds, rays are traced out to max_distance.
+    pub bounds: Option<Aabb>,
+    // The fraction of the distance to step each time, below one for fields that overestimate their distances.
+    pub step: f64,
+    pub max_steps: usize,
+    // How close to the surface a ray has to get to count as hitting it.
+    pub precision: f64,
+    pub max_distance: f64,
+    pub material: Box<Material>
+}
+
+impl Sdf
+{
+    pub fn new<F: DistanceField + 'static, T: Material + 'static>(field: F, material: T) -> Sdf
+    {
+        Sdf
+        {
+            field: Box::new(field),
+            bounds: None,
+            step: 1.0,
+            max_steps: 256,
+            precision: 1e-4,
+            max_distance: 100.0,
+            material: Box::new(material)
+        }
+    }
+
+    // Pins down where the distance changes sign between two times, with the sign at the first as given.
+    fn refine(&self, ray: Ray, near: f64, far: f64, outside: bool) -> f64
+    {
+        let (mut near, mut far) = (near, far);
+        for _ in 0..60
+        {
+            let middle = (near + far) / 2.0;
+            if (self.field.distance(ray.translate_to(middle)) > 0.0) == outside { near = middle } else { far = middle }
+        }
+        (near + far) / 2.0
+    }
+}
+
+impl Renderable for Sdf
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let (near, far) = match self.bounds
+        {
+            Some(bounds) => bounds.clip(ray, min_t, max_t)?,
+            None => (min_t, max_t)
+        };
+        let length = ray.direction.length();
+        let far = far.min(min_t + self.max_distance / length);
+
+        let mut t = near;
+        for _ in 0..self.max_steps
+        {
+            if t >= far
+            {
+                return None;
+            }
+            let distance = self.field.distance(ray.translate_to(t));
+            if distance.abs() < self.precision
+            {
+                // Close enough to bracket the crossing by stepping a little further, and then find it exactly. Rays
+                // heading away again only skimmed the surface, and are taken to hit where they came closest. Others
+                // crossing it at a shallow angle may need to get closer first.
+                let outside = distance > 0.0;
+                let beyond = t + 4.0 * self.precision / length;
+                let beyond_distance = self.field.distance(ray.translate_to(beyond));
+                let hit = if !self.field.signed()
+                {
+                    // Estimates give no sign to bracket a crossing with, so rays still closing in hit where they are,
+                    // and ones leaving the surface, as when setting off from it, carry on past it.
+                    if beyond_distance < distance { t } else { t = beyond; continue; }
+                }
+                else if (beyond_distance > 0.0) != outside
+                {
+                    self.refine(ray, t, beyond, outside)
+                }
+                else if beyond_distance.abs() >= distance.abs()
+                {
+                    t
+                }
+                else
+                {
+                    t += distance.abs() * self.step / length;
+                    continue;
+                };
+                if hit <= min_t
+                {
+                    t = beyond;
+                    continue;
+                }
+                if hit >= max_t
+                {
+                    return None;
+                }
+
+                let point = ray.translate_to(hit);
+                // Estimates are only good to around the precision, and finer differences of them are mostly noise.
+                let step = if self.field.signed() { 1e-6 * (1.0 + point.length()) } else { self.precision };
+                let normal = distance::gradient(&*self.field, point, step);
+                return Some(HitResult
+                {
+                    origin: point,
+                    normal: normal.normalized(),
+                    t: hit,
+                    time: ray.time,
+                    uv: self.field.coordinates(point),
+                    material: &*self.material
+                });
+            }
+            // Inside, the distance is negative, and stepping by its size heads for the way out.
+            t += distance.abs() * self.step / length;
+        }
+
+        None
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "sdf"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        self.bounds
+    }
+}
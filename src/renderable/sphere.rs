@@ -1,12 +1,15 @@
-use vector3::Vector3;
+use std::f64::consts::PI;
+use vector3::{ZERO, Vector3};
 use ray::Ray;
 use material::Material;
-use renderable::{Renderable, HitResult};
+use renderable::{Renderable, HitResult, Interval, Aabb};
 
 pub struct Sphere
 {
     pub origin: Vector3,
     pub radius: f64,
+    // How far the sphere moves in a unit of time, with origin being where it is at time zero.
+    pub velocity: Vector3,
     pub material: Box<Material>
 }
 
@@ -14,15 +17,20 @@ impl Sphere
 {
     pub fn new<T: Material + 'static>(origin: Vector3, radius: f64, material: T) -> Sphere
     {
-        Sphere { origin: origin, radius: radius, material: Box::new(material) }
+        Sphere { origin: origin, radius: radius, velocity: ZERO, material: Box::new(material) }
     }
-}
 
-impl Renderable for Sphere
-{
-    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    // A sphere moving in a straight line from start at time zero to end at time one, and on along the same line at
+    // the same speed at any other time.
+    pub fn moving<T: Material + 'static>(start: Vector3, end: Vector3, radius: f64, material: T) -> Sphere
     {
-        let vec = ray.origin - self.origin;
+        Sphere { origin: start, radius: radius, velocity: end - start, material: Box::new(material) }
+    }
+
+    // The two times the ray crosses the sphere, nearest first, if it doesn't miss or only touch it.
+    fn crossings(&self, ray: Ray) -> Option<(f64, f64)>
+    {
+        let vec = ray.origin - (self.origin + self.velocity * ray.time);
         let a = ray.direction.dot(ray.direction);
         let b = vec.dot(ray.direction);
         let c = vec.dot(vec) - self.radius * self.radius;
@@ -30,22 +38,57 @@ impl Renderable for Sphere
 
         if discriminant > 0.0
         {
-            for t in [(-b - discriminant.sqrt()) / a, (-b + discriminant.sqrt()) / a].iter()
-            {
-                if *t > min_t && *t < max_t
-                {
-                    let point = ray.translate_to(*t);
-                    return Some(HitResult
-                    {
-                        origin: point,
-                        normal: (point - self.origin) / self.radius,
-                        t: *t,
-                        material: &*self.material
-                    });
-                }
-            }
+            Some(((-b - discriminant.sqrt()) / a, (-b + discriminant.sqrt()) / a))
+        }
+        else
+        {
+            None
         }
-        
-        None
+    }
+
+    fn surface(&self, ray: Ray, t: f64) -> HitResult
+    {
+        let point = ray.translate_to(t);
+        let normal = (point - (self.origin + self.velocity * ray.time)) / self.radius;
+        HitResult
+        {
+            origin: point,
+            normal: normal,
+            t: t,
+            time: ray.time,
+            // Longitude around the y axis, and latitude from the bottom.
+            uv: (0.5 + normal.z.atan2(normal.x) / (2.0 * PI), 0.5 + normal.y.clamp(-1.0, 1.0).asin() / PI),
+            material: &*self.material
+        }
+    }
+}
+
+impl Renderable for Sphere
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let (near, far) = self.crossings(ray)?;
+        [near, far].iter().find(|&&t| t > min_t && t < max_t).map(|&t| self.surface(ray, t))
+    }
+
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        Some(self.crossings(ray).map(|(near, far)| Interval { enter: self.surface(ray, near), exit: self.surface(ray, far) }).into_iter().collect())
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "sphere"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        let radius = Vector3::new(self.radius, self.radius, self.radius);
+        if self.velocity.length_sqr() == 0.0 { Some(Aabb::new(self.origin - radius, self.origin + radius)) } else { None }
     }
 }
\ No newline at end of file
@@ -0,0 +1,890 @@
+// Property tests run every primitive against a few thousand random rays, half of them aimed at the shape, and check
+// the invariants the integrator relies on for every hit reported.
+use std::env;
+use std::f64;
+use std::fs;
+use std::rc::Rc;
+use rand::{SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+use animation::Track;
+use distance::{self, DistanceField};
+use distance::domain::Twist;
+use distance::fractal::Mandelbulb;
+use distance::shapes::{Capsule, Cuboid};
+use material::lambert::Lambert;
+use ray::Ray;
+use renderable::{Renderable, Aabb, Frame, EPSILON, swept};
+use renderable::cone::Cone;
+use renderable::cube::Cube;
+use renderable::csg::{Csg, Operation};
+use renderable::cylinder::Cylinder;
+use renderable::disk::Disk;
+use renderable::heightfield::Heightfield;
+use renderable::keyframed::Keyframed;
+use renderable::plane::Plane;
+use renderable::plane_bounded::PlaneBounded;
+use renderable::sdf::Sdf;
+use renderable::quartic::{Quartic, Term};
+use renderable::sphere::Sphere;
+use renderable::torus::Torus;
+use renderable::triangle::Triangle;
+use transform::Transform;
+use vector3::{ZERO, ONE, Vector3};
+
+const CASES: usize = 5000;
+
+struct Shape
+{
+    renderable: Box<Renderable>,
+    // A point the shape is around, which some of the random rays are aimed at.
+    target: Vector3,
+    // Whether a ray aimed at the target from this point must hit.
+    must_hit: Box<Fn(Vector3) -> bool>,
+    surface_distance: Box<Fn(Vector3) -> f64>,
+    normal_is_correct: Box<Fn(Vector3, Vector3) -> bool>
+}
+
+fn random_vector(rng: &mut SmallRng, scale: f64) -> Vector3
+{
+    Vector3::new(rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5) * (2.0 * scale)
+}
+
+fn random_direction(rng: &mut SmallRng) -> Vector3
+{
+    loop
+    {
+        let v = random_vector(rng, 1.0);
+        if v.length_sqr() > 0.01 && v.length_sqr() < 1.0
+        {
+            return v.normalized();
+        }
+    }
+}
+
+fn describe(v: Vector3) -> String
+{
+    format!("({}, {}, {})", v.x, v.y, v.z)
+}
+
+fn check<G: FnMut(&mut SmallRng) -> Shape>(name: &str, mut generate: G)
+{
+    let mut rng = SmallRng::seed_from_u64(1);
+    for case in 0..CASES
+    {
+        let shape = generate(&mut rng);
+        let origin = random_vector(&mut rng, 10.0);
+        let aimed = case % 2 == 0;
+        let direction = if aimed { shape.target - origin } else { random_direction(&mut rng) } * rng.random_range(0.1..4.0);
+        let ray = Ray { origin: origin, direction: direction, time: 0.0 };
+        let min_time = if case % 3 == 0 { rng.random_range(0.0..2.0) } else { EPSILON };
+        let max_time = if case % 5 == 0 { rng.random_range(min_time..min_time + 4.0) } else { f64::MAX };
+        let context = format!("{} case {}: ray from {} along {}, time {:e} to {:e}", name, case, describe(origin), describe(direction), min_time, max_time);
+
+        let h = match shape.renderable.test_hit(ray, min_time, max_time)
+        {
+            Some(h) => h,
+            None =>
+            {
+                assert!(!(aimed && min_time == EPSILON && max_time == f64::MAX && (shape.must_hit)(origin)), "{} missed", context);
+                continue;
+            }
+        };
+
+        let tolerance = 1e-6 * (1.0 + h.origin.length() + origin.length());
+        assert!(h.t >= min_time && h.t <= max_time, "{} hit at time {}", context, h.t);
+        assert!((h.origin - ray.translate_to(h.t)).length() < tolerance, "{} hit {} isn't at time {}", context, describe(h.origin), h.t);
+        assert!((h.normal.length() - 1.0).abs() < 1e-9, "{} normal {} isn't unit length", context, describe(h.normal));
+        assert!((shape.surface_distance)(h.origin) < tolerance, "{} hit {} isn't on the surface", context, describe(h.origin));
+        assert!((shape.normal_is_correct)(h.origin, h.normal), "{} normal {} is wrong at {}", context, describe(h.normal), describe(h.origin));
+
+        // Nothing may be reported before the first hit, and anything reported after it must respect the new minimum.
+        assert!(shape.renderable.test_hit(ray, min_time, h.t * (1.0 - 1e-9) - 1e-12).is_none(), "{} has a hit before {}", context, h.t);
+        if let Some(next) = shape.renderable.test_hit(ray, h.t + 1e-6, max_time)
+        {
+            assert!(next.t > h.t && next.t <= max_time, "{} next hit at {} isn't after {}", context, next.t, h.t);
+        }
+    }
+}
+
+#[test]
+fn sphere()
+{
+    check("Sphere", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let radius = rng.random_range(0.1..3.0);
+        Shape
+        {
+            renderable: Box::new(Sphere::new(center, radius, Lambert::new(ONE))),
+            target: center,
+            must_hit: Box::new(move |origin| (origin - center).length() > radius),
+            surface_distance: Box::new(move |p| ((p - center).length() - radius).abs()),
+            normal_is_correct: Box::new(move |p, n| (n - (p - center).normalized()).length() < 1e-6)
+        }
+    });
+}
+
+#[test]
+fn cube()
+{
+    check("Cube", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let half = Vector3::new(rng.random_range(0.05..1.5), rng.random_range(0.05..1.5), rng.random_range(0.05..1.5));
+        let distance = move |p: Vector3|
+        {
+            let d = p - center;
+            let outside = Vector3::new(d.x.abs() - half.x, d.y.abs() - half.y, d.z.abs() - half.z);
+            let clamped = Vector3::new(outside.x.max(0.0), outside.y.max(0.0), outside.z.max(0.0));
+            clamped.length() + outside.x.max(outside.y).max(outside.z).min(0.0).abs()
+        };
+        Shape
+        {
+            renderable: Box::new(Cube::new(center, 2.0 * half.x, 2.0 * half.y, 2.0 * half.z, Lambert::new(ONE))),
+            target: center,
+            must_hit: Box::new(|_| true),
+            surface_distance: Box::new(distance),
+            // The normal must be axis aligned, point outwards, and belong to a face the point actually lies on.
+            normal_is_correct: Box::new(move |p, n|
+            {
+                let d = p - center;
+                let axis_aligned = (n.x.abs() == 1.0) as i32 + (n.y.abs() == 1.0) as i32 + (n.z.abs() == 1.0) as i32 == 1;
+                let face = n.x * d.x / half.x + n.y * d.y / half.y + n.z * d.z / half.z;
+                axis_aligned && (face - 1.0).abs() < 1e-6
+            })
+        }
+    });
+}
+
+#[test]
+fn keyframed_cube()
+{
+    // A transformed cube is checked in its own space, where it's the same as the cube above.
+    check("Keyframed", |rng|
+    {
+        let half = Vector3::new(rng.random_range(0.05..1.5), rng.random_range(0.05..1.5), rng.random_range(0.05..1.5));
+        let rotation = Vector3::new(rng.random_range(-180.0..180.0), rng.random_range(-180.0..180.0), rng.random_range(-180.0..180.0));
+        let transform = Transform::new(random_vector(rng, 5.0), rotation, rng.random_range(0.2..3.0));
+        let cube = Cube::new(ZERO, 2.0 * half.x, 2.0 * half.y, 2.0 * half.z, Lambert::new(ONE));
+        Shape
+        {
+            renderable: Box::new(Keyframed::new(cube, Track::constant(transform))),
+            target: transform.translation,
+            must_hit: Box::new(|_| true),
+            surface_distance: Box::new(move |p|
+            {
+                let d = transform.inverse_point(p);
+                let outside = Vector3::new(d.x.abs() - half.x, d.y.abs() - half.y, d.z.abs() - half.z);
+                let clamped = Vector3::new(outside.x.max(0.0), outside.y.max(0.0), outside.z.max(0.0));
+                (clamped.length() + outside.x.max(outside.y).max(outside.z).min(0.0).abs()) * transform.scale
+            }),
+            normal_is_correct: Box::new(move |p, n|
+            {
+                let (d, n) = (transform.inverse_point(p), transform.unrotate(n));
+                let axis_aligned = (n.x.abs() > 1.0 - 1e-9) as i32 + (n.y.abs() > 1.0 - 1e-9) as i32 + (n.z.abs() > 1.0 - 1e-9) as i32 == 1;
+                let face = n.x * d.x / half.x + n.y * d.y / half.y + n.z * d.z / half.z;
+                axis_aligned && (face - 1.0).abs() < 1e-6
+            })
+        }
+    });
+}
+
+#[test]
+fn moving_shapes_are_where_they_are_at_the_ray_time()
+{
+    let sphere = Sphere::moving(Vector3::new(-2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0), 0.5, Lambert::new(ONE));
+    let cube = Cube::moving(Vector3::new(-2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0), 1.0, 1.0, 1.0, Lambert::new(ONE));
+    let turning = Keyframed::new(Cube::new(Vector3::new(2.0, 0.0, 0.0), 1.0, 1.0, 1.0, Lambert::new(ONE)),
+        Track::linear(vec![(0.0, Transform::new(ZERO, ZERO, 1.0)), (1.0, Transform::new(ZERO, Vector3::new(0.0, 0.0, 180.0), 1.0))]));
+    let down = |x: f64, time: f64| Ray { origin: Vector3::new(x, 5.0, 0.0), direction: Vector3::new(0.0, -1.0, 0.0), time: time };
+
+    for shape in [&sphere as &Renderable, &cube].iter()
+    {
+        assert!(shape.test_hit(down(-2.0, 0.0), EPSILON, f64::MAX).is_some());
+        assert!(shape.test_hit(down(-2.0, 0.5), EPSILON, f64::MAX).is_none());
+        assert!(shape.test_hit(down(0.0, 0.5), EPSILON, f64::MAX).is_some());
+        assert!(shape.test_hit(down(2.0, 1.0), EPSILON, f64::MAX).is_some());
+        // Unlike keyframes, the motion carries on past the end.
+        assert!(shape.test_hit(down(2.0, 2.0), EPSILON, f64::MAX).is_none());
+        assert!(shape.test_hit(down(6.0, 2.0), EPSILON, f64::MAX).is_some());
+    }
+
+    // Halfway through a half turn about z, the cube has swung up out of the way, and it stays put after the last key.
+    assert!(turning.test_hit(down(2.0, 0.0), EPSILON, f64::MAX).is_some());
+    assert!(turning.test_hit(down(2.0, 0.5), EPSILON, f64::MAX).is_none());
+    assert!(turning.test_hit(down(0.0, 0.5), EPSILON, f64::MAX).unwrap().origin.y > 1.9);
+    assert!(turning.test_hit(down(-2.0, 2.0), EPSILON, f64::MAX).is_some());
+}
+
+#[test]
+fn plane()
+{
+    check("Plane", |rng|
+    {
+        let origin = random_vector(rng, 5.0);
+        let normal = random_direction(rng);
+        Shape
+        {
+            renderable: Box::new(Plane::new(origin, normal * rng.random_range(0.2..5.0), Lambert::new(ONE))),
+            target: origin,
+            must_hit: Box::new(|_| false),
+            surface_distance: Box::new(move |p| (p - origin).dot(normal).abs()),
+            normal_is_correct: Box::new(move |_, n| (n - normal).length() < 1e-9)
+        }
+    });
+}
+
+#[test]
+fn plane_bounded()
+{
+    check("PlaneBounded", |rng|
+    {
+        let origin = random_vector(rng, 5.0);
+        let normal = if rng.random::<f64>() < 0.3 { Vector3::new(0.0, 1.0, 0.0) } else { random_direction(rng) };
+        let width = rng.random_range(0.1..4.0);
+        let depth = rng.random_range(0.1..4.0);
+        let plane = PlaneBounded::new(origin, normal * rng.random_range(0.2..5.0), width, depth, Lambert::new(ONE));
+        let (tangent, bitangent) = (plane.tangent, plane.bitangent);
+        Shape
+        {
+            renderable: Box::new(plane),
+            target: origin,
+            must_hit: Box::new(|_| false),
+            surface_distance: Box::new(move |p|
+            {
+                let local = p - origin;
+                let outside_width = (local.dot(tangent).abs() - width / 2.0).max(0.0);
+                let outside_depth = (local.dot(bitangent).abs() - depth / 2.0).max(0.0);
+                local.dot(normal).abs() + outside_width + outside_depth
+            }),
+            normal_is_correct: Box::new(move |_, n| (n - normal).length() < 1e-9)
+        }
+    });
+}
+
+#[test]
+fn triangle()
+{
+    check("Triangle", |rng|
+    {
+        let a = random_vector(rng, 5.0);
+        let b = a + random_vector(rng, 2.0);
+        let c = a + random_vector(rng, 2.0);
+        let normal = (b - a).cross(c - a).normalized();
+        Shape
+        {
+            renderable: Box::new(Triangle::new(a, b, c, Lambert::new(ONE))),
+            target: (a + b + c) / 3.0,
+            must_hit: Box::new(|_| false),
+            surface_distance: Box::new(move |p|
+            {
+                // Twice the area of the sub-triangles sums to twice the triangle's area only for points inside it.
+                let area = |x: Vector3, y: Vector3, z: Vector3| (y - x).cross(z - x).length();
+                let plane = (p - a).dot(normal).abs();
+                plane + (area(p, a, b) + area(p, b, c) + area(p, c, a) - area(a, b, c)).abs()
+            }),
+            normal_is_correct: Box::new(move |_, n| (n - normal).length() < 1e-9)
+        }
+    });
+}
+
+#[test]
+fn plane_bounded_facing_up_spans_x_and_z()
+{
+    let plane = PlaneBounded::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0), 4.0, 2.0, Lambert::new(ONE));
+    assert!((plane.tangent.x.abs() - 1.0).abs() < 1e-12 && (plane.bitangent.z.abs() - 1.0).abs() < 1e-12);
+
+    let down = |x: f64, z: f64| Ray { origin: Vector3::new(x, 1.0, z), direction: Vector3::new(0.0, -1.0, 0.0), time: 0.0 };
+    assert!(plane.test_hit(down(1.9, 0.9), EPSILON, f64::MAX).is_some());
+    assert!(plane.test_hit(down(2.1, 0.0), EPSILON, f64::MAX).is_none());
+    assert!(plane.test_hit(down(0.0, 1.1), EPSILON, f64::MAX).is_none());
+}
+
+// A line segment between two points in the half plane of distance from an axis and height along it, and the outward
+// normal of its side in the same plane.
+type Segment = ((f64, f64), (f64, f64), (f64, f64));
+
+// A shape made by turning a profile of segments around the axis of a frame.
+fn revolved(renderable: Box<Renderable>, frame: Frame, sweep: f64, segments: Vec<Segment>, closed: bool) -> Shape
+{
+    let profile = move |p: Vector3|
+    {
+        let local = p - frame.origin;
+        let (x, y) = (local.dot(frame.tangent), local.dot(frame.bitangent));
+        ((x * x + y * y).sqrt(), local.dot(frame.axis), Vector3::new(x, y, 0.0))
+    };
+    let segment_distance = |(r, z): (f64, f64), &(a, b, _): &Segment|
+    {
+        let (dr, dz) = (b.0 - a.0, b.1 - a.1);
+        let f = (((r - a.0) * dr + (z - a.1) * dz) / (dr * dr + dz * dz)).clamp(0.0, 1.0);
+        ((r - a.0 - f * dr).powi(2) + (z - a.1 - f * dz).powi(2)).sqrt()
+    };
+    let distance_segments = segments.clone();
+    let target = frame.origin + frame.axis * ((segments[0].0).1 + (segments[0].1).1) / 2.0;
+    Shape
+    {
+        renderable: renderable,
+        target: target,
+        must_hit: Box::new(move |_| closed),
+        surface_distance: Box::new(move |p|
+        {
+            let (r, z, local) = profile(p);
+            // Right on the axis, the angle around it is only rounding error.
+            let outside_sweep = if r < 1e-9 || swept(local.x, local.y, sweep).is_some() { 0.0 } else { 1.0 };
+            distance_segments.iter().map(|s| segment_distance((r, z), s)).fold(f64::MAX, f64::min) + outside_sweep
+        }),
+        // The normal must match the side of some segment the point lies on.
+        normal_is_correct: Box::new(move |p, n|
+        {
+            let (r, z, local) = profile(p);
+            let radial = if r > 0.0 { frame.world_vector(local / r) } else { ZERO };
+            segments.iter().any(|s| segment_distance((r, z), s) < 1e-6 * (1.0 + p.length()) &&
+                (n - (radial * (s.2).0 + frame.axis * (s.2).1)).length() < 1e-6)
+        })
+    }
+}
+
+fn random_sweep(rng: &mut SmallRng) -> f64
+{
+    if rng.random::<f64>() < 0.5 { 360.0 } else { rng.random_range(30.0..350.0) }
+}
+
+#[test]
+fn cylinder()
+{
+    check("Cylinder", |rng|
+    {
+        let base = random_vector(rng, 5.0);
+        let top = base + random_direction(rng) * rng.random_range(0.1..3.0);
+        let radius = rng.random_range(0.05..1.5);
+        let capped = rng.random::<f64>() < 0.5;
+        let mut cylinder = Cylinder::new(base, top, radius, capped, Lambert::new(ONE));
+        cylinder.sweep = if capped { 360.0 } else { random_sweep(rng) };
+        let (frame, height, sweep) = (cylinder.frame, cylinder.height, cylinder.sweep);
+        let mut segments = vec![((radius, 0.0), (radius, height), (1.0, 0.0))];
+        if capped
+        {
+            segments.push(((0.0, 0.0), (radius, 0.0), (0.0, -1.0)));
+            segments.push(((0.0, height), (radius, height), (0.0, 1.0)));
+        }
+        revolved(Box::new(cylinder), frame, sweep, segments, capped)
+    });
+}
+
+#[test]
+fn cone()
+{
+    check("Cone", |rng|
+    {
+        let base = random_vector(rng, 5.0);
+        let top = base + random_direction(rng) * rng.random_range(0.1..3.0);
+        let base_radius = rng.random_range(0.05..1.5);
+        let top_radius = if rng.random::<f64>() < 0.3 { 0.0 } else { rng.random_range(0.0..1.5) };
+        let capped = rng.random::<f64>() < 0.5;
+        let mut cone = Cone::new(base, top, base_radius, top_radius, capped, Lambert::new(ONE));
+        cone.sweep = if capped { 360.0 } else { random_sweep(rng) };
+        let (frame, height, sweep) = (cone.frame, cone.height, cone.sweep);
+        let slant = Vector3::new(height, base_radius - top_radius, 0.0).normalized();
+        let mut segments = vec![((base_radius, 0.0), (top_radius, height), (slant.x, slant.y))];
+        if capped
+        {
+            segments.push(((0.0, 0.0), (base_radius, 0.0), (0.0, -1.0)));
+            segments.push(((0.0, height), (top_radius, height), (0.0, 1.0)));
+        }
+        revolved(Box::new(cone), frame, sweep, segments, capped)
+    });
+}
+
+#[test]
+fn disk()
+{
+    check("Disk", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let normal = random_direction(rng);
+        let radius = rng.random_range(0.1..3.0);
+        let inner_radius = if rng.random::<f64>() < 0.5 { 0.0 } else { rng.random_range(0.0..radius) };
+        let mut disk = Disk::annulus(center, normal, inner_radius, radius, Lambert::new(ONE));
+        disk.sweep = random_sweep(rng);
+        let (frame, sweep) = (disk.frame, disk.sweep);
+        revolved(Box::new(disk), frame, sweep, vec![((inner_radius, 0.0), (radius, 0.0), (0.0, 1.0))], false)
+    });
+}
+
+#[test]
+fn revolved_shapes_are_parameterized_around_and_along_their_axis()
+{
+    // Standing on the origin along y, the tangent is x and the bitangent -z, so a quarter turn around is towards -z.
+    let mut cylinder = Cylinder::new(ZERO, Vector3::new(0.0, 2.0, 0.0), 1.0, true, Lambert::new(ONE));
+    let towards = |origin: Vector3, direction: Vector3| Ray { origin: origin, direction: direction, time: 0.0 };
+    let side = cylinder.test_hit(towards(Vector3::new(0.0, 0.5, -5.0), Vector3::new(0.0, 0.0, 1.0)), EPSILON, f64::MAX).unwrap();
+    assert!((side.uv.0 - 0.25).abs() < 1e-9 && (side.uv.1 - 0.25).abs() < 1e-9);
+    let top = cylinder.test_hit(towards(Vector3::new(0.5, 5.0, 0.0), Vector3::new(0.0, -1.0, 0.0)), EPSILON, f64::MAX).unwrap();
+    assert!((top.normal.y - 1.0).abs() < 1e-9 && top.uv.0.abs() < 1e-9 && (top.uv.1 - 0.5).abs() < 1e-9);
+
+    // With half swept away, rays through the missing half reach the far side, seeing it from inside.
+    cylinder.sweep = 180.0;
+    cylinder.capped = false;
+    let inside = cylinder.test_hit(towards(Vector3::new(0.0, 0.5, 5.0), Vector3::new(0.0, 0.0, -1.0)), EPSILON, f64::MAX).unwrap();
+    assert!((inside.origin.z + 1.0).abs() < 1e-9 && (inside.uv.0 - 0.5).abs() < 1e-9);
+
+    let annulus = Disk::annulus(ZERO, Vector3::new(0.0, 1.0, 0.0), 0.5, 1.0, Lambert::new(ONE));
+    assert!(annulus.test_hit(towards(Vector3::new(0.2, 1.0, 0.0), Vector3::new(0.0, -1.0, 0.0)), EPSILON, f64::MAX).is_none());
+    let ring = annulus.test_hit(towards(Vector3::new(0.75, 1.0, 0.0), Vector3::new(0.0, -1.0, 0.0)), EPSILON, f64::MAX).unwrap();
+    assert!((ring.uv.1 - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn bounds_contain_every_hit()
+{
+    let mut rng = SmallRng::seed_from_u64(2);
+    for _ in 0..200
+    {
+        let base = random_vector(&mut rng, 5.0);
+        let top = base + random_direction(&mut rng) * rng.random_range(0.1..3.0);
+        let shapes: Vec<Box<Renderable>> = vec![
+            Box::new(Cylinder::new(base, top, rng.random_range(0.05..1.5), true, Lambert::new(ONE))),
+            Box::new(Cone::new(base, top, rng.random_range(0.05..1.5), rng.random_range(0.0..1.5), true, Lambert::new(ONE))),
+            Box::new(Disk::new(base, top - base, rng.random_range(0.05..1.5), Lambert::new(ONE))),
+            Box::new(Sphere::new(base, rng.random_range(0.05..1.5), Lambert::new(ONE))),
+            Box::new(Triangle::new(base, top, base + random_vector(&mut rng, 2.0), Lambert::new(ONE)))
+        ];
+        for shape in shapes.iter()
+        {
+            let bounds = shape.bounds().unwrap();
+            for _ in 0..20
+            {
+                let origin = random_vector(&mut rng, 10.0);
+                let ray = Ray { origin: origin, direction: base + random_vector(&mut rng, 1.0) - origin, time: 0.0 };
+                if let Some(h) = shape.test_hit(ray, EPSILON, f64::MAX)
+                {
+                    let p = h.origin;
+                    let margin = 1e-9;
+                    assert!(p.x >= bounds.min.x - margin && p.y >= bounds.min.y - margin && p.z >= bounds.min.z - margin, "{} hit below its bounds", shape.kind());
+                    assert!(p.x <= bounds.max.x + margin && p.y <= bounds.max.y + margin && p.z <= bounds.max.z + margin, "{} hit above its bounds", shape.kind());
+                    assert!(bounds.padded(EPSILON).hit(ray, EPSILON, f64::MAX), "{} hit outside its bounds", shape.kind());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn torus()
+{
+    check("Torus", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let major_radius = rng.random_range(0.2..2.0);
+        let minor_radius = rng.random_range(0.02..1.0) * major_radius;
+        let torus = Torus::new(center, random_direction(rng), major_radius, minor_radius, Lambert::new(ONE));
+        let frame = torus.frame;
+        // The nearest point on the circle through the middle of the ring.
+        let core = move |p: Vector3|
+        {
+            let local = p - frame.origin;
+            let around = local - frame.axis * local.dot(frame.axis);
+            frame.origin + around.normalized() * major_radius
+        };
+        Shape
+        {
+            renderable: Box::new(torus),
+            // Inside the ring, so every ray aimed there has to cross its surface.
+            target: center + frame.tangent * major_radius,
+            must_hit: Box::new(|_| true),
+            surface_distance: Box::new(move |p| ((p - core(p)).length() - minor_radius).abs()),
+            normal_is_correct: Box::new(move |p, n| (n - (p - core(p)).normalized()).length() < 1e-6)
+        }
+    });
+}
+
+#[test]
+fn torus_finds_grazing_hits()
+{
+    // Rays skimming the top of the ring ever more closely still hit it twice, just either side of where they touch.
+    let torus = Torus::new(ZERO, Vector3::new(0.0, 1.0, 0.0), 1000.0, 1.0, Lambert::new(ONE));
+    for &gap in [1e-2, 1e-4, 1e-6].iter()
+    {
+        let ray = Ray { origin: Vector3::new(-5000.0, 1.0 - gap, 1000.0), direction: Vector3::new(1.0, 0.0, 0.0), time: 0.0 };
+        let first = torus.test_hit(ray, EPSILON, f64::MAX).unwrap();
+        let second = torus.test_hit(ray, first.t + 1e-9, f64::MAX).unwrap();
+        // Where the ray is as far from the middle of the ring as the surface is at that height.
+        let spread = ((1000.0 + (1.0 - (1.0 - gap) * (1.0 - gap)).sqrt()).powi(2) - 1000.0 * 1000.0_f64).sqrt();
+        assert!((first.origin.x + spread).abs() < 1e-3 * spread && (second.origin.x - spread).abs() < 1e-3 * spread,
+            "gap {}: hits at {} and {}", gap, first.origin.x, second.origin.x);
+        assert!(first.normal.y > 0.99);
+    }
+    let above = Ray { origin: Vector3::new(-5000.0, 1.0 + 1e-6, 1000.0), direction: Vector3::new(1.0, 0.0, 0.0), time: 0.0 };
+    assert!(torus.test_hit(above, EPSILON, f64::MAX).is_none());
+}
+
+#[test]
+fn quartic()
+{
+    // A cube with rounded edges and corners, where x⁴ + y⁴ + z⁴ = s⁴ around a center.
+    check("Quartic", |rng|
+    {
+        let c = random_vector(rng, 5.0);
+        let size: f64 = rng.random_range(0.1..2.0);
+        let mut terms = vec![Term::new(-size.powi(4), 0, 0, 0)];
+        for (axis, &center) in [c.x, c.y, c.z].iter().enumerate()
+        {
+            // (x - c)⁴ expanded by the binomial theorem.
+            for (power, &binomial) in [1.0, 4.0, 6.0, 4.0, 1.0].iter().enumerate()
+            {
+                let mut powers = [0; 3];
+                powers[axis] = 4 - power as u32;
+                terms.push(Term::new(binomial * (-center).powi(power as i32), powers[0], powers[1], powers[2]));
+            }
+        }
+        let half = Vector3::new(size, size, size) * 1.01;
+        let value = move |p: Vector3| { let d = p - c; d.x.powi(4) + d.y.powi(4) + d.z.powi(4) - size.powi(4) };
+        let gradient = move |p: Vector3| { let d = p - c; Vector3::new(d.x.powi(3), d.y.powi(3), d.z.powi(3)) * 4.0 };
+        Shape
+        {
+            renderable: Box::new(Quartic::new(terms, Aabb::new(c - half, c + half), Lambert::new(ONE))),
+            target: c,
+            must_hit: Box::new(|_| true),
+            // Near the surface, the value divided by how fast it changes is the distance to it.
+            surface_distance: Box::new(move |p| value(p).abs() / gradient(p).length()),
+            normal_is_correct: Box::new(move |p, n| (n - gradient(p).normalized()).length() < 1e-6)
+        }
+    });
+}
+
+#[test]
+fn quartic_matches_sphere()
+{
+    let sphere = Sphere::new(Vector3::new(1.0, 2.0, 3.0), 1.5, Lambert::new(ONE));
+    // (x - 1)² + (y - 2)² + (z - 3)² - 1.5², which is only quadratic, but lower degrees are fine too.
+    let terms = vec![Term::new(1.0, 2, 0, 0), Term::new(-2.0, 1, 0, 0), Term::new(1.0, 0, 2, 0), Term::new(-4.0, 0, 1, 0),
+        Term::new(1.0, 0, 0, 2), Term::new(-6.0, 0, 0, 1), Term::new(1.0 + 4.0 + 9.0 - 2.25, 0, 0, 0)];
+    let quartic = Quartic::new(terms, sphere.bounds().unwrap().padded(0.1), Lambert::new(ONE));
+    let mut rng = SmallRng::seed_from_u64(3);
+    for _ in 0..500
+    {
+        let origin = random_vector(&mut rng, 10.0);
+        let ray = Ray { origin: origin, direction: sphere.origin + random_vector(&mut rng, 2.0) - origin, time: 0.0 };
+        match (sphere.test_hit(ray, EPSILON, f64::MAX), quartic.test_hit(ray, EPSILON, f64::MAX))
+        {
+            (Some(a), Some(b)) => assert!((a.t - b.t).abs() < 1e-9 && (a.normal - b.normal).length() < 1e-9),
+            (None, None) => {},
+            _ => panic!("Sphere and quartic disagree about whether a ray from {} hits", describe(origin))
+        }
+    }
+}
+
+fn sphere_distance(center: Vector3, radius: f64) -> impl Fn(Vector3) -> f64
+{
+    move |p| (p - center).length() - radius
+}
+
+fn cube_distance(center: Vector3, half: Vector3) -> impl Fn(Vector3) -> f64
+{
+    move |p|
+    {
+        let d = p - center;
+        let outside = Vector3::new(d.x.abs() - half.x, d.y.abs() - half.y, d.z.abs() - half.z);
+        let clamped = Vector3::new(outside.x.max(0.0), outside.y.max(0.0), outside.z.max(0.0));
+        clamped.length() + outside.x.max(outside.y).max(outside.z).min(0.0)
+    }
+}
+
+#[test]
+fn csg()
+{
+    // The signed distance to the combination is the smaller of the two for a union and the larger for an intersection,
+    // taking the second inside out for a difference. It's only exact near the surface, which is all that's checked.
+    check("Csg", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let radius = rng.random_range(0.2..1.5);
+        let offset = center + random_vector(rng, radius);
+        let half = Vector3::new(rng.random_range(0.1..1.5), rng.random_range(0.1..1.5), rng.random_range(0.1..1.5));
+        let operation = [Operation::Union, Operation::Intersection, Operation::Difference][rng.random_range(0..3)];
+        let (sphere, cube) = (sphere_distance(center, radius), cube_distance(offset, half));
+        let distance = Rc::new(move |p: Vector3| match operation
+        {
+            Operation::Union => sphere(p).min(cube(p)),
+            Operation::Intersection => sphere(p).max(cube(p)),
+            Operation::Difference => sphere(p).max(-cube(p))
+        });
+        let gradient_distance = distance.clone();
+        let normal = move |p: Vector3|
+        {
+            let distance = &gradient_distance;
+            let e = 1e-7;
+            let axis = |v: Vector3| (distance(p + v * e) - distance(p - v * e)) / (2.0 * e);
+            Vector3::new(axis(Vector3::new(1.0, 0.0, 0.0)), axis(Vector3::new(0.0, 1.0, 0.0)), axis(Vector3::new(0.0, 0.0, 1.0)))
+        };
+        Shape
+        {
+            renderable: Box::new(Csg::new(operation, Sphere::new(center, radius, Lambert::new(ONE)), Cube::new(offset, 2.0 * half.x, 2.0 * half.y, 2.0 * half.z, Lambert::new(ONE)))),
+            target: center,
+            must_hit: Box::new(move |_| operation == Operation::Union),
+            surface_distance: Box::new(move |p| distance(p).abs()),
+            // Along edges where the surfaces meet the gradient is a blend of the two, so only points clear of them count.
+            normal_is_correct: Box::new(move |p, n| { let g = normal(p); (g.length() - 1.0).abs() > 1e-3 || (n - g).length() < 1e-4 })
+        }
+    });
+}
+
+#[test]
+fn intervals_match_successive_hits()
+{
+    let mut rng = SmallRng::seed_from_u64(4);
+    for _ in 0..200
+    {
+        let base = random_vector(&mut rng, 3.0);
+        let top = base + random_direction(&mut rng) * rng.random_range(0.2..2.0);
+        let radius = rng.random_range(0.1..1.0);
+        let solids: Vec<Box<Renderable>> = vec![
+            Box::new(Sphere::new(base, radius, Lambert::new(ONE))),
+            Box::new(Cube::new(base, radius, 2.0 * radius, 0.5, Lambert::new(ONE))),
+            Box::new(Cylinder::new(base, top, radius, true, Lambert::new(ONE))),
+            Box::new(Cone::new(base, top, radius, 0.5 * radius, true, Lambert::new(ONE))),
+            Box::new(Torus::new(base, top - base, 1.0, radius * 0.5, Lambert::new(ONE))),
+            Box::new(Csg::difference(Sphere::new(base, 1.0, Lambert::new(ONE)), Torus::new(base, top - base, 1.0, radius * 0.5, Lambert::new(ONE))))
+        ];
+        for solid in solids.iter()
+        {
+            let origin = random_vector(&mut rng, 6.0);
+            let ray = Ray { origin: origin, direction: base + random_vector(&mut rng, 1.0) - origin, time: 0.0 };
+            let mut crossings = Vec::new();
+            for interval in solid.intervals(ray).unwrap()
+            {
+                assert!(interval.enter.t < interval.exit.t, "{} interval runs backwards", solid.kind());
+                assert!(interval.enter.normal.dot(ray.direction) <= 0.0 && interval.exit.normal.dot(ray.direction) >= 0.0,
+                    "{} normals don't face out of the solid", solid.kind());
+                crossings.push(interval.enter.t);
+                crossings.push(interval.exit.t);
+            }
+
+            // Stepping from hit to hit from the start of the ray finds the same crossings after it.
+            let mut t = EPSILON;
+            for &expected in crossings.iter().filter(|&&c| c > EPSILON + 1e-9)
+            {
+                let h = solid.test_hit(ray, t, f64::MAX).unwrap_or_else(|| panic!("{} missed a crossing at {}", solid.kind(), expected));
+                assert!((h.t - expected).abs() < 1e-6, "{} hit at {} instead of {}", solid.kind(), h.t, expected);
+                t = h.t + 1e-9;
+            }
+            assert!(solid.test_hit(ray, t, f64::MAX).is_none(), "{} has a hit past its last crossing", solid.kind());
+        }
+    }
+}
+
+#[test]
+fn differences_are_lined_with_the_cutting_material()
+{
+    let (outer, inner) = (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+    let box_with_hole = Csg::difference(Cube::new(ZERO, 2.0, 2.0, 2.0, Lambert::new(outer)),
+        Cylinder::new(Vector3::new(0.0, -2.0, 0.0), Vector3::new(0.0, 2.0, 0.0), 0.5, true, Lambert::new(inner)));
+    let across = Ray { origin: Vector3::new(-5.0, 0.0, 0.0), direction: Vector3::new(1.0, 0.0, 0.0), time: 0.0 };
+
+    let intervals = box_with_hole.intervals(across).unwrap();
+    assert_eq!(intervals.len(), 2);
+    let times: Vec<f64> = intervals.iter().flat_map(|i| vec![i.enter.t, i.exit.t]).collect();
+    for (t, expected) in times.iter().zip([4.0, 4.5, 5.5, 6.0].iter())
+    {
+        assert!((t - expected).abs() < 1e-9);
+    }
+
+    // The wall of the hole faces into it, back towards the ray, and takes the cylinder's material.
+    let wall = intervals[0].exit;
+    assert!((wall.normal - Vector3::new(1.0, 0.0, 0.0)).length() < 1e-9);
+    assert!((wall.material.albedo(wall) - inner).length() < 1e-9);
+    assert!((intervals[0].enter.material.albedo(intervals[0].enter) - outer).length() < 1e-9);
+
+    // Down the hole there's nothing to hit.
+    let down = Ray { origin: Vector3::new(0.0, 5.0, 0.0), direction: Vector3::new(0.0, -1.0, 0.0), time: 0.0 };
+    assert!(box_with_hole.test_hit(down, EPSILON, f64::MAX).is_none());
+}
+
+#[test]
+fn sdf()
+{
+    check("Sdf", |rng|
+    {
+        let center = random_vector(rng, 5.0);
+        let size = Vector3::new(rng.random_range(0.2..2.0), rng.random_range(0.2..2.0), rng.random_range(0.2..2.0));
+        let (kind, rounding, radius) = (rng.random_range(0..3), rng.random_range(0.0..0.1), rng.random_range(0.05..0.5));
+        let field = move || -> Box<DistanceField> { match kind
+        {
+            0 => Box::new(distance::shapes::Sphere::new(center, size.x)),
+            1 => Box::new(Cuboid::rounded(center, size, rounding)),
+            _ => Box::new(Capsule::new(center - size / 2.0, center + size / 2.0, radius))
+        } };
+        let (distance_field, normal_field) = (field(), field());
+        Shape
+        {
+            renderable: Box::new(Sdf { field: field(), ..Sdf::new(Cuboid::new(ZERO, ONE), Lambert::new(ONE)) }),
+            target: center,
+            must_hit: Box::new(|_| true),
+            surface_distance: Box::new(move |p| distance_field.distance(p).abs()),
+            normal_is_correct: Box::new(move |p, n| (n - distance::gradient(&*normal_field, p, 1e-6).normalized()).length() < 1e-5)
+        }
+    });
+}
+
+#[test]
+fn sdf_sphere_matches_sphere()
+{
+    let sphere = Sphere::new(Vector3::new(1.0, 2.0, 3.0), 1.5, Lambert::new(ONE));
+    let mut sdf = Sdf::new(distance::shapes::Sphere::new(sphere.origin, 1.5), Lambert::new(ONE));
+    sdf.bounds = sphere.bounds();
+    let mut rng = SmallRng::seed_from_u64(5);
+    for _ in 0..500
+    {
+        let origin = random_vector(&mut rng, 10.0);
+        let ray = Ray { origin: origin, direction: sphere.origin + random_vector(&mut rng, 0.8) - origin, time: 0.0 };
+        let (a, b) = (sphere.test_hit(ray, EPSILON, f64::MAX).unwrap(), sdf.test_hit(ray, EPSILON, f64::MAX).unwrap());
+        assert!((a.t - b.t).abs() < 1e-9 && (a.normal - b.normal).length() < 1e-6);
+    }
+}
+
+#[test]
+fn sdf_steps_carefully_through_stretched_space()
+{
+    // A twisted bar overestimates its distances, so full steps can jump right through a corner of it.
+    let bar = || Twist::new(Cuboid::new(ZERO, Vector3::new(3.0, 6.0, 0.3)), 120.0);
+    let ray = Ray { origin: Vector3::new(-1.8, -1.3, -10.0), direction: Vector3::new(0.3, 0.1, 1.0), time: 0.0 };
+    let mut careful = Sdf::new(bar(), Lambert::new(ONE));
+    careful.step = 0.5;
+    careful.max_steps = 1000;
+    let h = careful.test_hit(ray, EPSILON, f64::MAX).unwrap();
+    assert!(bar().distance(h.origin).abs() < 1e-9);
+    assert!((0..1000).all(|i| bar().distance(ray.translate_to(h.t * i as f64 / 1000.0)) > 0.0));
+
+    let hasty = Sdf::new(bar(), Lambert::new(ONE));
+    assert!(hasty.test_hit(ray, EPSILON, f64::MAX).is_none_or(|x| x.t > h.t + 0.1));
+}
+
+#[test]
+fn sdf_traces_fractals_to_their_surfaces()
+{
+    let bulb = || Mandelbulb::new(8.0, 12);
+    let sdf = Sdf::new(bulb(), Lambert::new(ONE));
+    let mut rng = SmallRng::seed_from_u64(6);
+    let mut facing = 0;
+    for _ in 0..200
+    {
+        let origin = random_vector(&mut rng, 10.0);
+        let origin = origin * (4.0 / origin.length());
+        let ray = Ray { origin: origin, direction: random_vector(&mut rng, 0.3) - origin, time: 0.0 };
+        let h = sdf.test_hit(ray, EPSILON, f64::MAX).unwrap();
+        assert!(bulb().distance(h.origin) < sdf.precision && h.uv == bulb().coordinates(h.origin));
+        if h.normal.dot(ray.direction) < 0.0
+        {
+            facing += 1;
+        }
+
+        // Rays setting off from the surface start out within the precision of it, but aren't taken to hit it there.
+        let away = Ray { origin: h.origin, direction: h.normal, time: 0.0 };
+        assert!(sdf.test_hit(away, 1e-6, f64::MAX).is_none_or(|x| x.t > sdf.precision));
+    }
+    // Normals of rough surfaces are only ever as good as the differences they're estimated from.
+    assert!(facing > 190);
+}
+
+#[test]
+fn heightfield_matches_its_triangles()
+{
+    let mut rng = SmallRng::seed_from_u64(7);
+    let (columns, rows) = (13, 9);
+    let heights: Vec<f64> = (0..columns * rows).map(|_| rng.random::<f64>()).collect();
+    let (origin, size) = (Vector3::new(-3.0, -1.0, -2.0), Vector3::new(6.0, 1.5, 4.0));
+    let field = Heightfield::new(columns, rows, heights.clone(), origin, size, Lambert::new(ONE));
+
+    let sample = |i: usize, j: usize| origin + Vector3::new(i as f64 * size.x / 12.0, heights[j * columns + i] * size.y, j as f64 * size.z / 8.0);
+    let mut triangles = Vec::new();
+    for j in 0..rows - 1
+    {
+        for i in 0..columns - 1
+        {
+            triangles.push(Triangle::new(sample(i, j), sample(i + 1, j), sample(i + 1, j + 1), Lambert::new(ONE)));
+            triangles.push(Triangle::new(sample(i, j), sample(i + 1, j + 1), sample(i, j + 1), Lambert::new(ONE)));
+        }
+    }
+
+    let bounds = field.bounds().unwrap();
+    let mut hits = 0;
+    for _ in 0..2000
+    {
+        let origin = random_vector(&mut rng, 6.0);
+        let target = Vector3::new(rng.random_range(-3.0..3.0), rng.random_range(-1.0..0.5), rng.random_range(-2.0..2.0));
+        let ray = Ray { origin: origin, direction: target - origin, time: 0.0 };
+        let expected = triangles.iter().filter_map(|t| t.test_hit(ray, EPSILON, f64::MAX)).map(|h| h.t).fold(f64::MAX, f64::min);
+        match field.test_hit(ray, EPSILON, f64::MAX)
+        {
+            Some(h) =>
+            {
+                assert!((h.t - expected).abs() < 1e-9, "{} isn't {}", h.t, expected);
+                assert!(bounds.padded(1e-9).hit(ray, h.t - 1e-9, h.t + 1e-9));
+                assert!((0.0..=1.0).contains(&h.uv.0) && (0.0..=1.0).contains(&h.uv.1));
+                hits += 1;
+            },
+            None => assert_eq!(expected, f64::MAX)
+        }
+    }
+    assert!(hits > 1000);
+}
+
+#[test]
+fn heightfield_normals_and_coordinates_follow_the_surface()
+{
+    // A tilted plane, rising a unit over the whole field along x, has the same normal everywhere.
+    let heights = (0..5 * 4).map(|n| (n % 5) as f64 / 4.0).collect();
+    let field = Heightfield::new(5, 4, heights, ZERO, Vector3::new(2.0, 1.0, 3.0), Lambert::new(ONE));
+    let ray = Ray { origin: Vector3::new(0.5, 5.0, 2.25), direction: Vector3::new(0.0, -1.0, 0.0), time: 0.0 };
+    let h = field.test_hit(ray, EPSILON, f64::MAX).unwrap();
+    assert!((h.t - 4.75).abs() < 1e-12);
+    assert!((h.normal - Vector3::new(-1.0, 2.0, 0.0).normalized()).length() < 1e-12);
+    assert!((h.uv.0 - 0.25).abs() < 1e-12 && (h.uv.1 - 0.75).abs() < 1e-12);
+
+    // Across a ridge, normals blend smoothly from one side to the other rather than jumping at the crease.
+    let ridge = Heightfield::new(3, 2, vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0], ZERO, ONE, Lambert::new(ONE));
+    let normal_at = |x: f64| ridge.test_hit(Ray { origin: Vector3::new(x, 2.0, 0.5), direction: Vector3::new(0.0, -1.0, 0.0), time: 0.0 }, EPSILON, f64::MAX).unwrap().normal;
+    assert!((normal_at(0.4999) - normal_at(0.5001)).length() < 1e-3);
+    assert!((normal_at(0.5) - Vector3::new(0.0, 1.0, 0.0)).length() < 1e-9);
+    assert!(normal_at(0.2).x < 0.0 && normal_at(0.8).x > 0.0);
+}
+
+#[test]
+fn heightfields_load_from_images()
+{
+    // Sixteen bit samples are stored with their high byte first.
+    let path = env::temp_dir().join("raytracer_test_heights.png");
+    lodepng::encode_file(&path, &[0x00u8, 0x00, 0x80, 0x00, 0xff, 0xff, 0x12, 0x34], 2, 2, lodepng::ColorType::GREY, 16).unwrap();
+    let field = Heightfield::load(path.to_str().unwrap(), ZERO, Vector3::new(1.0, 2.0, 1.0), Lambert::new(ONE)).unwrap();
+    let height = |x: f64, z: f64| 5.0 - field.test_hit(Ray { origin: Vector3::new(x, 5.0, z), direction: Vector3::new(0.0, -1.0, 0.0), time: 0.0 }, EPSILON, f64::MAX).unwrap().t;
+    assert!(height(0.0, 0.0).abs() < 1e-12 && (height(1.0, 0.0) - 2.0 * 0x8000 as f64 / 65535.0).abs() < 1e-12);
+    assert!((height(0.0, 1.0) - 2.0).abs() < 1e-12 && (height(1.0, 1.0) - 2.0 * 0x1234 as f64 / 65535.0).abs() < 1e-12);
+
+    // Colors count by how bright they are overall.
+    lodepng::encode32_file(&path, &[rgb::RGBA8 { r: 255, g: 0, b: 0, a: 255 }; 4], 2, 2).unwrap();
+    let field = Heightfield::load(path.to_str().unwrap(), ZERO, ONE, Lambert::new(ONE)).unwrap();
+    assert!((field.bounds().unwrap().max.y - 1.0 / 3.0).abs() < 1e-12);
+    fs::remove_file(&path).unwrap();
+
+    assert!(Heightfield::load("missing.png", ZERO, ONE, Lambert::new(ONE)).is_err());
+}
+
+#[test]
+fn transformed_solids_can_be_combined()
+{
+    // A cube turned 45 degrees about y, so that its edges face along x and z, joined to a sphere off to its side.
+    let turned = Keyframed::new(Cube::new(ZERO, 1.0, 1.0, 1.0, Lambert::new(ONE)), Track::constant(Transform::new(ZERO, Vector3::new(0.0, 45.0, 0.0), 1.0)));
+    let union = Csg::union(turned, Sphere::new(Vector3::new(2.0, 0.0, 0.0), 0.3, Lambert::new(ONE)));
+    let ray = Ray { origin: Vector3::new(-5.0, 0.0, 0.2), direction: Vector3::new(1.0, 0.0, 0.0), time: 0.0 };
+    let intervals = union.intervals(ray).unwrap();
+    assert_eq!(intervals.len(), 2);
+
+    // Across the turned cube, x and z add up to the distance from its center to an edge.
+    let edge = 0.5f64.sqrt() - 0.2;
+    let (enter, exit) = (intervals[0].enter, intervals[0].exit);
+    assert!((enter.t - (5.0 - edge)).abs() < 1e-9 && (exit.t - (5.0 + edge)).abs() < 1e-9);
+    assert!((enter.normal - Vector3::new(-1.0, 0.0, 1.0).normalized()).length() < 1e-9);
+    assert!((exit.normal - Vector3::new(1.0, 0.0, 1.0).normalized()).length() < 1e-9);
+    let sphere = 0.3f64 * 0.3 - 0.2 * 0.2;
+    assert!((intervals[1].enter.t - (7.0 - sphere.sqrt())).abs() < 1e-9);
+}
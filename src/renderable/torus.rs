@@ -0,0 +1,112 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use polynomial;
+use renderable::{Renderable, HitResult, Interval, Aabb, Frame, swept};
+
+// A ring around an axis, made of every point within the minor radius of the circle with the major radius.
+pub struct Torus
+{
+    pub frame: Frame,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+    pub material: Box<Material>
+}
+
+impl Torus
+{
+    pub fn new<T: Material + 'static>(center: Vector3, axis: Vector3, major_radius: f64, minor_radius: f64, material: T) -> Torus
+    {
+        Torus { frame: Frame::new(center, axis), major_radius: major_radius, minor_radius: minor_radius, material: Box::new(material) }
+    }
+
+    // Every time between the two given that the ray crosses the surface, in order.
+    fn crossings(&self, ray: Ray, min_t: f64, max_t: f64) -> Vec<f64>
+    {
+        // Only the part of the ray inside the bounds can hit, which also keeps the search for roots short.
+        let (near, far) = match self.bounds().unwrap().padded(1e-9 * self.major_radius).clip(ray, min_t, max_t)
+        {
+            Some(span) => span,
+            None => return Vec::new()
+        };
+
+        // The quartic is solved along a unit direction from the point on the ray closest to the center, so that its
+        // coefficients stay small wherever the ray starts from.
+        let local = self.frame.local_ray(ray);
+        let length = local.direction.length();
+        let d = local.direction / length;
+        let start = -local.origin.dot(d);
+        let o = local.origin + d * start;
+
+        // Points on the surface are where (x² + y² + z² + R² - r²)² = 4R²(x² + y²), for major radius R and minor r.
+        let (major, minor) = (self.major_radius * self.major_radius, self.minor_radius * self.minor_radius);
+        let k = o.dot(d);
+        let m = o.dot(o) + major - minor;
+        let coefficients = [
+            m * m - 4.0 * major * (o.x * o.x + o.y * o.y),
+            4.0 * k * m - 8.0 * major * (o.x * d.x + o.y * d.y),
+            4.0 * k * k + 2.0 * m - 4.0 * major * (d.x * d.x + d.y * d.y),
+            4.0 * k,
+            1.0
+        ];
+
+        let roots = polynomial::roots(&coefficients, near * length - start, far * length - start);
+        roots.iter().map(|s| (s + start) / length).filter(|&t| t > min_t && t < max_t).collect()
+    }
+
+    fn surface(&self, ray: Ray, t: f64) -> HitResult
+    {
+        let point = self.frame.local_ray(ray).translate_to(t);
+
+        // The normal points away from the nearest point on the circle through the middle of the ring.
+        let distance = (point.x * point.x + point.y * point.y).sqrt();
+        let normal = if distance > 0.0
+        {
+            point - Vector3::new(point.x, point.y, 0.0) * (self.major_radius / distance)
+        }
+        else
+        {
+            Vector3::new(0.0, 0.0, point.z)
+        };
+        HitResult
+        {
+            origin: ray.translate_to(t),
+            normal: self.frame.world_vector(normal.normalized()),
+            t: t,
+            time: ray.time,
+            // Around the axis, then around the ring from its outer edge.
+            uv: (swept(point.x, point.y, 360.0).unwrap(), swept(distance - self.major_radius, point.z, 360.0).unwrap()),
+            material: &*self.material
+        }
+    }
+}
+
+impl Renderable for Torus
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        self.crossings(ray, min_t, max_t).first().map(|&t| self.surface(ray, t))
+    }
+
+    // Rays only grazing the surface may cross it an odd number of times, in which case the last crossing is dropped.
+    fn intervals(&self, ray: Ray) -> Option<Vec<Interval>>
+    {
+        let crossings = self.crossings(ray, -f64::MAX, f64::MAX);
+        Some(crossings.chunks_exact(2).map(|pair| Interval { enter: self.surface(ray, pair[0]), exit: self.surface(ray, pair[1]) }).collect())
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "torus"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        Some(Aabb::around_circle(self.frame.origin, self.frame.axis, self.major_radius).padded(self.minor_radius))
+    }
+}
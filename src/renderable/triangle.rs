@@ -0,0 +1,85 @@
+use vector3::Vector3;
+use ray::Ray;
+use material::Material;
+use renderable::{Renderable, HitResult, Aabb, EPSILON};
+
+pub struct Triangle
+{
+    pub a: Vector3,
+    pub b: Vector3,
+    pub c: Vector3,
+    pub normal: Vector3,
+    pub material: Box<Material>
+}
+
+impl Triangle
+{
+    // The normal faces the side from which a, b and c appear counter-clockwise.
+    pub fn new<T: Material + 'static>(a: Vector3, b: Vector3, c: Vector3, material: T) -> Triangle
+    {
+        let normal = (b - a).cross(c - a).normalized();
+        Triangle { a: a, b: b, c: c, normal: normal, material: Box::new(material) }
+    }
+}
+
+impl Renderable for Triangle
+{
+    fn test_hit(&self, ray: Ray, min_t: f64, max_t: f64) -> Option<HitResult>
+    {
+        let edge_ab = self.b - self.a;
+        let edge_ac = self.c - self.a;
+        let p = ray.direction.cross(edge_ac);
+        let determinant = edge_ab.dot(p);
+        if determinant.abs() < EPSILON * EPSILON
+        {
+            return None;
+        }
+
+        let inverse = 1.0 / determinant;
+        let s = ray.origin - self.a;
+        let u = s.dot(p) * inverse;
+        if !(0.0..=1.0).contains(&u)
+        {
+            return None;
+        }
+
+        let q = s.cross(edge_ab);
+        let v = ray.direction.dot(q) * inverse;
+        if v < 0.0 || u + v > 1.0
+        {
+            return None;
+        }
+
+        let t = edge_ac.dot(q) * inverse;
+        if t > min_t && t < max_t
+        {
+            return Some(HitResult
+            {
+                origin: ray.translate_to(t),
+                normal: self.normal,
+                t: t,
+                time: ray.time,
+                // Barycentric, towards b and c.
+                uv: (u, v),
+                material: &*self.material
+            });
+        }
+
+        None
+    }
+
+    fn kind(&self) -> &'static str
+    {
+        "triangle"
+    }
+
+    fn materials(&self) -> Vec<&Material>
+    {
+        vec![&*self.material]
+    }
+
+    fn bounds(&self) -> Option<Aabb>
+    {
+        Some(Aabb::around(&[self.a, self.b, self.c]))
+    }
+}
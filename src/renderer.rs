@@ -0,0 +1,355 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use random;
+use aov::Aovs;
+use camera::Camera;
+use film::Film;
+use filter::Filter;
+use ray::Ray;
+use renderable::HitResult;
+use scene::Scene;
+use stats::{self, Counters};
+use vector3::{self, Vector3};
+
+const TILE_SIZE: usize = 32;
+
+pub struct Renderer
+{
+    pub width: usize,
+    pub height: usize,
+    pub ray_count: usize,
+    pub bounce_max: i32,
+    pub aovs: bool,
+    pub seed: u64,
+    // Caps on any one sample's light, applied separately to what reaches the camera within one bounce and what takes
+    // longer. Clamping loses energy, but keeps rare bright paths from turning into fireflies.
+    pub clamp_direct: Option<f64>,
+    pub clamp_indirect: Option<f64>,
+    // Logs where non-finite samples turn up.
+    pub debug: bool
+}
+
+pub struct RenderResult
+{
+    pub film: Film,
+    pub aovs: Option<Aovs>,
+    pub counters: Counters
+}
+
+impl Renderer
+{
+    pub fn new(width: usize, height: usize, ray_count: usize, bounce_max: i32) -> Renderer
+    {
+        Renderer
+        {
+            width: width,
+            height: height,
+            ray_count: ray_count,
+            bounce_max: bounce_max,
+            aovs: false,
+            seed: 0,
+            clamp_direct: None,
+            clamp_indirect: None,
+            debug: false
+        }
+    }
+
+    pub fn render(&self, scene: &Scene, camera: &Camera, filter: Box<Filter>) -> RenderResult
+    {
+        let film = Mutex::new(Film::new(self.width, self.height, filter));
+        let aovs = if self.aovs { Some(Mutex::new(Aovs::new(self.width, self.height))) } else { None };
+        let tiles_x = self.width.div_ceil(TILE_SIZE);
+        let tiles_y = self.height.div_ceil(TILE_SIZE);
+        let next_tile = AtomicUsize::new(0);
+        let counters = Mutex::new(Counters::default());
+        let thread_count = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+        thread::scope(|s|
+        {
+            for _ in 0..thread_count
+            {
+                s.spawn(||
+                {
+                    stats::take();
+                    loop
+                    {
+                        let tile_index = next_tile.fetch_add(1, Ordering::Relaxed);
+                        if tile_index >= tiles_x * tiles_y
+                        {
+                            break;
+                        }
+
+                        random::seed(self.seed ^ (tile_index as u64).wrapping_mul(0x9e3779b97f4a7c15));
+                        let x0 = (tile_index % tiles_x) * TILE_SIZE;
+                        let y0 = (tile_index / tiles_x) * TILE_SIZE;
+                        let x1 = (x0 + TILE_SIZE).min(self.width);
+                        let y1 = (y0 + TILE_SIZE).min(self.height);
+                        let mut tile = film.lock().unwrap().tile(x0, y0, x1, y1);
+                        let mut aov_tile = aovs.as_ref().map(|a| a.lock().unwrap().tile(x0, y0, x1, y1));
+
+                        for y in y0..y1
+                        {
+                            for x in x0..x1
+                            {
+                                for _ in 0..self.ray_count
+                                {
+                                    let film_x = (x as f64) + random::next_f64();
+                                    let film_y = (y as f64) + random::next_f64();
+                                    // Parts of the image the camera doesn't cover are black.
+                                    let sample = camera.get_weighted_ray(film_x / (self.width as f64), 1.0 - film_y / (self.height as f64));
+                                    let (color, first_hit) = match sample
+                                    {
+                                        Some((ray, weight)) =>
+                                        {
+                                            let (direct, indirect, first_hit) = get_light(ray, scene, self.bounce_max, 0);
+                                            (self.sample_color(x, y, weight * direct, weight * indirect), first_hit)
+                                        },
+                                        None => (vector3::ZERO, None)
+                                    };
+                                    if let Some(ref mut a) = aov_tile
+                                    {
+                                        let material = first_hit.as_ref().and_then(|&(index, ref h)| scene.material_id(index, h.material));
+                                        a.add_sample(x, y, first_hit, material, color);
+                                    }
+                                    tile.add_sample(film_x, film_y, color);
+                                }
+                            }
+                        }
+
+                        film.lock().unwrap().merge(tile);
+                        if let Some(a) = aov_tile
+                        {
+                            aovs.as_ref().unwrap().lock().unwrap().merge(a);
+                        }
+                    }
+                    counters.lock().unwrap().merge(&stats::take());
+                });
+            }
+        });
+
+        RenderResult
+        {
+            film: film.into_inner().unwrap(),
+            aovs: aovs.map(|a| a.into_inner().unwrap()),
+            counters: counters.into_inner().unwrap()
+        }
+    }
+
+    // Combines a sample's direct and indirect light, dropping whichever of them isn't finite so that it can't poison
+    // the pixel it lands in, and clamping each.
+    fn sample_color(&self, x: usize, y: usize, direct: Vector3, indirect: Vector3) -> Vector3
+    {
+        let finite = direct.is_finite() && indirect.is_finite();
+        stats::record(|c|
+        {
+            c.camera_rays += 1;
+            if !finite
+            {
+                c.non_finite_samples += 1;
+            }
+        });
+        if !finite && self.debug
+        {
+            eprintln!("Non-finite sample at ({}, {}): direct ({}, {}, {}), indirect ({}, {}, {})",
+                x, y, direct.x, direct.y, direct.z, indirect.x, indirect.y, indirect.z);
+        }
+
+        let direct = if direct.is_finite() { clamp(direct, self.clamp_direct) } else { vector3::ZERO };
+        let indirect = if indirect.is_finite() { clamp(indirect, self.clamp_indirect) } else { vector3::ZERO };
+        direct + indirect
+    }
+}
+
+// Scales the color down so that no component is over the limit, which keeps its hue.
+fn clamp(color: Vector3, limit: Option<f64>) -> Vector3
+{
+    let max = color.x.max(color.y).max(color.z);
+    match limit
+    {
+        Some(limit) if max > limit => color * (limit / max),
+        _ => color
+    }
+}
+
+#[cfg(test)]
+pub fn get_color(ray: Ray, scene: &Scene, bounce_max: i32) -> Vector3
+{
+    let (direct, indirect, _) = get_light(ray, scene, bounce_max, 0);
+    direct + indirect
+}
+
+// Light arriving along a path, split into the part that reached the camera directly or after a single bounce,
+// and the rest. Depth counts the segments before this one. The hit the path starts with, and the index of what
+// it hit, come back too, for the AOVs.
+fn get_light(ray: Ray, scene: &Scene, bounce_max: i32, depth: i32) -> (Vector3, Vector3, Option<(usize, HitResult)>)
+{
+    let split = |light: Vector3| if depth <= 1 { (light, vector3::ZERO) } else { (vector3::ZERO, light) };
+    let hit_result = scene.test_hit_indexed(ray);
+    match hit_result
+    {
+        None =>
+        {
+            let (direct, indirect) = split(scene.background_color(ray));
+            (direct, indirect, None)
+        },
+        Some((_, h)) =>
+        {
+            if bounce_max < 0
+            {
+                stats::record(|c| c.paths_at_bounce_max += 1);
+                return (vector3::ZERO, vector3::ZERO, hit_result);
+            }
+
+            let (emitted_direct, emitted_indirect) = split(h.material.emitted(h));
+            let scatter_result = h.material.scatter(ray, h);
+            match scatter_result
+            {
+                None => (emitted_direct, emitted_indirect, hit_result),
+                Some(s) =>
+                {
+                    let (direct, indirect, _) = get_light(s.scattered, scene, bounce_max - 1, depth + 1);
+                    (emitted_direct + s.attenuation * direct, emitted_indirect + s.attenuation * indirect, hit_result)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::env;
+    use std::fs;
+    use lodepng;
+    use aov::AovFormat;
+    use filter;
+    use material::{Material, ScatterResult};
+    use material::metal::Metal;
+    use ray::Ray;
+    use renderable::HitResult;
+    use renderable::sphere::Sphere;
+    use renderer::{Renderer, clamp};
+    use scene::{Scene, Background};
+    use camera::perspective::Perspective;
+    use vector3::{ZERO, ONE, Vector3};
+
+    // Reflects straight back with an attenuation that's not a number.
+    struct Broken;
+
+    impl Material for Broken
+    {
+        fn scatter(&self, ray: Ray, hit_result: HitResult) -> Option<ScatterResult>
+        {
+            let scattered = Ray { origin: hit_result.origin, direction: -ray.direction, time: ray.time };
+            Some(ScatterResult { scattered: scattered, attenuation: Vector3::new(f64::NAN, 0.0, 0.0) })
+        }
+
+        fn albedo(&self, _: HitResult) -> Vector3
+        {
+            ONE
+        }
+    }
+
+    #[test]
+    fn clamp_keeps_hue()
+    {
+        let clamped = clamp(Vector3::new(4.0, 2.0, 1.0), Some(2.0));
+        assert!((clamped - Vector3::new(2.0, 1.0, 0.5)).length() < 1e-12);
+        assert!((clamp(Vector3::new(4.0, 2.0, 1.0), None) - Vector3::new(4.0, 2.0, 1.0)).length() < 1e-12);
+        assert!((clamp(Vector3::new(1.0, 0.5, 0.0), Some(2.0)) - Vector3::new(1.0, 0.5, 0.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn non_finite_samples_are_counted_and_dropped()
+    {
+        let mut scene = Scene::new();
+        scene.background = Background::Color(ONE);
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, -3.0), 1.0, Broken));
+        let camera = Perspective::new(ZERO, Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0), 90.0, 1.0, 0.0, 1.0);
+
+        let renderer = Renderer::new(8, 8, 2, 4);
+        let result = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap());
+        assert!(result.counters.non_finite_samples > 0 && result.counters.non_finite_samples < 8 * 8 * 2);
+        for color in result.film.colors()
+        {
+            assert!(color.is_finite());
+        }
+    }
+
+    #[test]
+    fn clamps_apply_to_direct_and_indirect_light_separately()
+    {
+        // A bright sky seen directly, and through a single bounce off a white sphere, is all direct light.
+        let mut scene = Scene::new();
+        scene.background = Background::Color(Vector3::new(10.0, 10.0, 10.0));
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, -3.0), 1.0, Metal::new(ONE, 0.0)));
+        let camera = Perspective::new(ZERO, Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0), 90.0, 1.0, 0.0, 1.0);
+
+        let mut renderer = Renderer::new(4, 4, 1, 4);
+        renderer.clamp_indirect = Some(1.0);
+        let colors = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).film.colors();
+        assert!(colors.iter().all(|c| (c.x - 10.0).abs() < 1e-9));
+
+        renderer.clamp_direct = Some(1.0);
+        let colors = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).film.colors();
+        assert!(colors.iter().all(|c| (c.x - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn material_ids_follow_the_scene_not_the_image()
+    {
+        // Only the second sphere is in view, so its material is the first one the image sees.
+        let mut scene = Scene::new();
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, 3.0), 1.0, Metal::new(ONE, 0.0)));
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, -3.0), 2.0, Metal::new(ONE, 0.0)));
+        let camera = Perspective::new(ZERO, Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0), 30.0, 1.0, 0.0, 1.0);
+
+        let mut renderer = Renderer::new(4, 4, 1, 4);
+        renderer.aovs = true;
+        let aovs = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).aovs.unwrap();
+        assert!(aovs.material_ids().iter().all(|&id| id == Some(1)));
+    }
+
+    #[test]
+    fn materials_without_data_get_their_own_ids()
+    {
+        // Boxes of a material with no fields share an address, but they're still different materials.
+        let mut scene = Scene::new();
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, 3.0), 1.0, Broken));
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, -3.0), 2.0, Broken));
+        let camera = Perspective::new(ZERO, Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0), 30.0, 1.0, 0.0, 1.0);
+
+        let mut renderer = Renderer::new(4, 4, 1, 4);
+        renderer.aovs = true;
+        let aovs = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).aovs.unwrap();
+        assert!(aovs.material_ids().iter().all(|&id| id == Some(1)));
+    }
+
+    #[test]
+    fn positions_are_scaled_to_what_was_hit()
+    {
+        // A sphere far from the origin, with sky around it.
+        let mut scene = Scene::new();
+        scene.add(Sphere::new(Vector3::new(0.0, 0.0, -10.0), 3.0, Metal::new(ONE, 0.0)));
+        let camera = Perspective::new(ZERO, Vector3::new(0.0, 0.0, -1.0), Vector3::new(0.0, 1.0, 0.0), 90.0, 1.0, 0.0, 1.0);
+        let mut renderer = Renderer::new(16, 16, 1, 4);
+        renderer.aovs = true;
+        let aovs = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).aovs.unwrap();
+        let prefix = env::temp_dir().join("raytracer_test_aovs");
+        let prefix = prefix.to_str().unwrap();
+        aovs.write(prefix, AovFormat::Png, &vec![ZERO; 16 * 16]).unwrap();
+        let path = format!("{}.position.png", prefix);
+        let image = lodepng::decode32_file(&path).unwrap();
+        for suffix in ["position", "normal", "depth", "albedo", "material_id", "object_id"].iter()
+        {
+            fs::remove_file(format!("{}.{}.png", prefix, suffix)).unwrap();
+        }
+
+        let ids = aovs.object_ids();
+        assert!(ids.iter().any(|id| id.is_none()));
+        let hits: Vec<_> = image.buffer.iter().zip(ids.iter()).filter(|&(_, id)| id.is_some()).map(|(p, _)| p.b).collect();
+        assert_eq!((*hits.iter().min().unwrap(), *hits.iter().max().unwrap()), (0, 255));
+        assert!(image.buffer.iter().zip(ids.iter()).filter(|&(_, id)| id.is_none()).all(|(p, _)| p.r == 0 && p.g == 0 && p.b == 0));
+    }
+}
@@ -1,31 +1,89 @@
 use std::f64;
+use std::ptr;
 use ray::Ray;
-use renderable::{Renderable, HitResult, EPSILON};
+use material::Material;
+use renderable::{Renderable, HitResult, Aabb, EPSILON};
+use stats;
+use vector3::{self, Vector3};
+
+pub enum Background
+{
+    Sky,
+    Color(Vector3)
+}
 
 pub struct Scene
 {
-    renderables: Vec<Box<Renderable>>
+    pub background: Background,
+    renderables: Vec<Box<Renderable>>,
+    // The bounds of each renderable, padded a little, so rays that miss them can skip the full test.
+    bounds: Vec<Option<Aabb>>,
+    // The ID of each renderable's first material. Materials are numbered in the order they're added, each
+    // renderable's in the order it lists them.
+    first_materials: Vec<usize>,
+    material_count: usize
 }
 
 impl Scene
 {
     pub fn new() -> Scene
     {
-        Scene { renderables: Vec::new() }
+        Scene { background: Background::Sky, renderables: Vec::new(), bounds: Vec::new(), first_materials: Vec::new(), material_count: 0 }
     }
 
     pub fn add<T: Renderable + 'static>(&mut self, renderable: T)
     {
+        self.bounds.push(renderable.bounds().map(|b| b.padded(EPSILON)));
+        self.first_materials.push(self.material_count);
+        self.material_count += renderable.materials().len();
         self.renderables.push(Box::new(renderable))
     }
 
+    // The ID of a material hit on the renderable with the given index, the same in every image of the scene. Only
+    // that renderable's own materials are compared, by type as well as address, since boxed materials without any
+    // data all share one address.
+    pub fn material_id(&self, index: usize, material: &Material) -> Option<usize>
+    {
+        self.renderables[index].materials().iter().position(|&m| ptr::eq(m, material)).map(|i| self.first_materials[index] + i)
+    }
+
+    pub fn background_color(&self, ray: Ray) -> Vector3
+    {
+        match self.background
+        {
+            Background::Sky =>
+            {
+                let direction = ray.direction.normalized();
+                let t = 0.5 * (direction.y + 1.0);
+                (1.0 - t) * vector3::ONE + t * Vector3{x: 0.5, y: 0.7, z: 1.0}
+            },
+            Background::Color(color) => color
+        }
+    }
+
     pub fn test_hit(&self, ray: Ray) -> Option<HitResult>
     {
-        let mut result: Option<HitResult> = None;
+        self.test_hit_indexed(ray).map(|(_, h)| h)
+    }
+
+    // Same as test_hit, but also returns the index of the renderable that was hit, in the order they were added.
+    pub fn test_hit_indexed(&self, ray: Ray) -> Option<(usize, HitResult)>
+    {
+        let mut result: Option<(usize, HitResult)> = None;
         let mut distance = f64::MAX;
+        stats::record(|c| c.rays += 1);
 
-        for renderable in self.renderables.iter()
+        for (index, renderable) in self.renderables.iter().enumerate()
         {
+            if let Some(ref bounds) = self.bounds[index]
+            {
+                if !bounds.hit(ray, EPSILON, f64::MAX)
+                {
+                    continue;
+                }
+            }
+            // Only tests that get past the bounds count.
+            stats::record(|c| c.add_intersection_tests(renderable.kind(), 1));
             let child_result = renderable.test_hit(ray, EPSILON, f64::MAX);
             match child_result
             {
@@ -35,7 +93,7 @@ impl Scene
                     let child_distance = (v.origin - ray.origin).length();
                     if child_distance < distance
                     {
-                        result = child_result;
+                        result = Some((index, v));
                         distance = child_distance;
                     }
                 }
@@ -0,0 +1,30 @@
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use material::dielectric::Dielectric;
+use renderable::plane::Plane;
+use renderable::plane_bounded::PlaneBounded;
+use renderable::sphere::Sphere;
+use renderable::cube::Cube;
+use scene::Scene;
+use vector3::Vector3;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.4, 0.8, 0.4))));
+    scene.add(PlaneBounded::new(Vector3::new(0.0, 0.25, 0.5), Vector3::new(-0.25, 0.5, 0.0), 0.5, 0.25, Lambert::new(Vector3::new(0.4, 0.4, 0.8))));
+    scene.add(Sphere::new(Vector3::new(-1.0, 0.5, -1.0), 0.5, Metal::new(Vector3::new(0.8, 0.6, 0.2), 0.2)));
+    scene.add(Sphere::new(Vector3::new(1.0, 0.5, 0.2), 0.35, Metal::new(Vector3::new(0.8, 0.2, 0.6), 0.05)));
+    scene.add(Cube::new(Vector3::new(-0.25, 0.5, -0.2), 0.5, 0.5, 0.5, Lambert::new(Vector3::new(0.8, 0.0, 0.0))));
+    scene.add(Cube::new(Vector3::new(0.5, 0.5, 0.5), 1.0, 0.5, 0.5, Dielectric::new(0.5)));
+
+    let camera_origin = Vector3{x: -0.75, y: 1.2, z: 1.0};
+    let camera_look_at = Vector3{x: 0.0, y: 0.5, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z:0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 72.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
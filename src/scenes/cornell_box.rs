@@ -0,0 +1,37 @@
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::diffuse_light::DiffuseLight;
+use renderable::cube::Cube;
+use scene::{Scene, Background};
+use scenes::add_quad;
+use vector3::{ZERO, Vector3};
+
+// The classic box, 555 units to a side and open towards the camera, lit only by a patch on the ceiling.
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let red = Lambert::new(Vector3::new(0.65, 0.05, 0.05));
+    let white = Lambert::new(Vector3::new(0.73, 0.73, 0.73));
+    let green = Lambert::new(Vector3::new(0.12, 0.45, 0.15));
+    let light = DiffuseLight::new(Vector3::new(15.0, 15.0, 15.0));
+
+    let mut scene = Scene::new();
+    scene.background = Background::Color(ZERO);
+
+    add_quad(&mut scene, Vector3::new(555.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 555.0), Vector3::new(0.0, 555.0, 0.0), green);
+    add_quad(&mut scene, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 555.0, 0.0), Vector3::new(0.0, 0.0, 555.0), red);
+    add_quad(&mut scene, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 555.0), Vector3::new(555.0, 0.0, 0.0), white.clone());
+    add_quad(&mut scene, Vector3::new(0.0, 555.0, 0.0), Vector3::new(555.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 555.0), white.clone());
+    add_quad(&mut scene, Vector3::new(0.0, 0.0, 555.0), Vector3::new(0.0, 555.0, 0.0), Vector3::new(555.0, 0.0, 0.0), white.clone());
+    add_quad(&mut scene, Vector3::new(213.0, 554.0, 227.0), Vector3::new(130.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 105.0), light);
+
+    scene.add(Cube::new(Vector3::new(212.5, 82.5, 147.5), 165.0, 165.0, 165.0, white.clone()));
+    scene.add(Cube::new(Vector3::new(347.5, 165.0, 377.5), 165.0, 330.0, 165.0, white));
+
+    let camera_origin = Vector3::new(278.0, 278.0, -800.0);
+    let camera_look_at = Vector3::new(278.0, 278.0, 0.0);
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        Vector3::new(0.0, 1.0, 0.0), 40.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
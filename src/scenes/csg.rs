@@ -0,0 +1,46 @@
+// The classic constructive solid geometry part: a cube rounded off by a sphere, with three cylinders bored through it,
+// next to a bowl made by cutting a sphere with a cube.
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::csg::Csg;
+use renderable::cube::Cube;
+use renderable::cylinder::Cylinder;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use scene::Scene;
+use vector3::Vector3;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    let center = Vector3::new(-0.4, 0.5, 0.0);
+    let drill = Lambert::new(Vector3::new(0.9, 0.7, 0.2));
+    let rounded = Csg::intersection(
+        Cube::new(center, 1.0, 1.0, 1.0, Lambert::new(Vector3::new(0.8, 0.25, 0.2))),
+        Sphere::new(center, 0.65, Lambert::new(Vector3::new(0.2, 0.5, 0.8))));
+    let bores = Csg::union(
+        Csg::union(
+            Cylinder::new(center - Vector3::new(0.6, 0.0, 0.0), center + Vector3::new(0.6, 0.0, 0.0), 0.28, true, drill.clone()),
+            Cylinder::new(center - Vector3::new(0.0, 0.6, 0.0), center + Vector3::new(0.0, 0.6, 0.0), 0.28, true, drill.clone())),
+        Cylinder::new(center - Vector3::new(0.0, 0.0, 0.6), center + Vector3::new(0.0, 0.0, 0.6), 0.28, true, drill));
+    scene.add(Csg::difference(rounded, bores));
+
+    // A hemisphere with its top cut off flat and hollowed out.
+    let bowl_center = Vector3::new(0.9, 0.4, 0.3);
+    let outside = Csg::difference(
+        Sphere::new(bowl_center, 0.4, Metal::new(Vector3::new(0.8, 0.8, 0.85), 0.1)),
+        Cube::new(bowl_center + Vector3::new(0.0, 0.5, 0.0), 1.0, 1.0, 1.0, Lambert::new(Vector3::new(0.9, 0.9, 0.9))));
+    scene.add(Csg::difference(outside, Sphere::new(bowl_center, 0.33, Lambert::new(Vector3::new(0.9, 0.9, 0.9)))));
+
+    let camera_origin = Vector3{x: 0.4, y: 1.6, z: 2.4};
+    let camera_look_at = Vector3{x: 0.1, y: 0.4, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 55.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
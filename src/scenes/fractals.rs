@@ -0,0 +1,50 @@
+// A Mandelbulb, a Mandelbox and a quaternion Julia set side by side, each colored along a ramp by its orbit trap.
+use animation::Track;
+use camera::perspective::Perspective;
+use distance::DistanceField;
+use distance::domain::Transformed;
+use distance::fractal::{Mandelbox, Mandelbulb, QuaternionJulia};
+use material::lambert::Lambert;
+use material::ramp::Ramp;
+use renderable::Aabb;
+use renderable::plane::Plane;
+use renderable::sdf::Sdf;
+use scene::Scene;
+use transform::Transform;
+use vector3::{ZERO, Vector3};
+
+// Shrinks a fractal reaching out to the given radius around the origin down to sit on the floor at x.
+fn place<F: DistanceField + 'static>(scene: &mut Scene, fractal: F, radius: f64, x: f64, colors: Track<Vector3>)
+{
+    let size = 0.45;
+    let center = Vector3::new(x, size, 0.0);
+    let placed = Transformed::new(fractal, Transform::new(center, Vector3::new(0.0, 30.0, 0.0), size / radius));
+    let mut sdf = Sdf::new(placed, Ramp::new(Lambert::new(Vector3::new(1.0, 1.0, 1.0)), colors));
+    sdf.precision = 2e-4;
+    // A box around the sphere the fractal fits in, whichever way it's turned.
+    sdf.bounds = Some(Aabb::new(center - Vector3::new(size, size, size), center + Vector3::new(size, size, size)));
+    scene.add(sdf);
+}
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(ZERO, Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    place(&mut scene, Mandelbulb::new(8.0, 12), 1.2, -1.0, Track::linear(vec![
+        (0.0, Vector3::new(0.9, 0.2, 0.1)), (0.5, Vector3::new(0.95, 0.8, 0.3)), (1.0, Vector3::new(0.3, 0.5, 0.9))]));
+    // Scale two fills the cube from -6 to 6, whose corners reach out this far.
+    place(&mut scene, Mandelbox::new(2.0, 12), 6.0 * 3.0f64.sqrt(), 0.0, Track::linear(vec![
+        (0.0, Vector3::new(0.2, 0.3, 0.8)), (0.5, Vector3::new(0.3, 0.8, 0.6)), (1.0, Vector3::new(0.9, 0.9, 0.9))]));
+    place(&mut scene, QuaternionJulia::new([-0.2, 0.6, 0.2, 0.2], 12), 1.2, 1.0, Track::linear(vec![
+        (0.0, Vector3::new(0.6, 0.1, 0.5)), (0.5, Vector3::new(0.9, 0.5, 0.2)), (1.0, Vector3::new(0.95, 0.9, 0.7))]));
+
+    let camera_origin = Vector3{x: 0.0, y: 1.2, z: 2.6};
+    let camera_look_at = Vector3{x: 0.0, y: 0.4, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 50.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
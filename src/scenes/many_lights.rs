@@ -0,0 +1,42 @@
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use material::diffuse_light::DiffuseLight;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use renderable::cube::Cube;
+use scene::{Scene, Background};
+use vector3::{ZERO, Vector3};
+
+// A dark room lit by a grid of small colored lights hanging over a few objects.
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.background = Background::Color(ZERO);
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.7, 0.7, 0.7))));
+
+    for i in 0..12
+    {
+        for j in 0..12
+        {
+            let hue = (i * 12 + j) as f64 / 144.0 * 6.0;
+            let color = Vector3::new(
+                (hue - 3.0).abs() - 1.0,
+                2.0 - (hue - 2.0).abs(),
+                2.0 - (hue - 4.0).abs());
+            let color = Vector3::new(color.x.clamp(0.0, 1.0), color.y.clamp(0.0, 1.0), color.z.clamp(0.0, 1.0));
+            let origin = Vector3::new(i as f64 - 5.5, 3.0, j as f64 - 5.5);
+            scene.add(Sphere::new(origin, 0.08, DiffuseLight::new(40.0 * color)));
+        }
+    }
+
+    scene.add(Sphere::new(Vector3::new(-1.5, 1.0, 0.0), 1.0, Metal::new(Vector3::new(0.9, 0.9, 0.9), 0.1)));
+    scene.add(Sphere::new(Vector3::new(1.5, 0.75, 0.5), 0.75, Lambert::new(Vector3::new(0.8, 0.8, 0.8))));
+    scene.add(Cube::new(Vector3::new(0.0, 0.5, -2.0), 1.0, 1.0, 1.0, Lambert::new(Vector3::new(0.6, 0.6, 0.9))));
+
+    let camera = Perspective::new(
+        Vector3::new(0.0, 2.0, 8.0), Vector3::new(0.0, 0.8, 0.0),
+        Vector3::new(0.0, 1.0, 0.0), 50.0, aspect, 0.0, 1.0);
+
+    (scene, camera)
+}
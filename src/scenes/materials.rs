@@ -0,0 +1,30 @@
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use material::dielectric::Dielectric;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use scene::Scene;
+use vector3::Vector3;
+
+// Rows of spheres sweeping metal fuzz and glass refraction index, in front of a row of diffuse ones.
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.8, 0.8, 0.8))));
+
+    for i in 0..5
+    {
+        let x = (i as f64 - 2.0) * 1.1;
+        let f = i as f64 / 4.0;
+        scene.add(Sphere::new(Vector3::new(x, 0.5, -1.2), 0.5, Metal::new(Vector3::new(0.9, 0.8, 0.6), f)));
+        scene.add(Sphere::new(Vector3::new(x, 0.5, 0.0), 0.5, Dielectric::new(1.0 + f * 1.4)));
+        scene.add(Sphere::new(Vector3::new(x, 0.5, 1.2), 0.5, Lambert::new(Vector3::new(0.9 - 0.7 * f, 0.2 + 0.3 * f, 0.2 + 0.7 * f))));
+    }
+
+    let camera = Perspective::new(
+        Vector3::new(0.0, 3.0, 6.0), Vector3::new(0.0, 0.4, 0.0),
+        Vector3::new(0.0, 1.0, 0.0), 45.0, aspect, 0.0, 1.0);
+
+    (scene, camera)
+}
@@ -0,0 +1,41 @@
+// Machined parts built from cylinders, cones, disks and annuli, including a cut away tube showing its inside.
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::plane::Plane;
+use renderable::cylinder::Cylinder;
+use renderable::cone::Cone;
+use renderable::disk::Disk;
+use scene::Scene;
+use vector3::Vector3;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    let steel = Metal::new(Vector3::new(0.8, 0.8, 0.85), 0.15);
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    // A bolt with a pointed end.
+    scene.add(Cylinder::new(Vector3::new(-1.0, 0.0, -0.3), Vector3::new(-1.0, 0.15, -0.3), 0.3, true, steel.clone()));
+    scene.add(Cylinder::new(Vector3::new(-1.0, 0.15, -0.3), Vector3::new(-1.0, 0.9, -0.3), 0.12, false, steel.clone()));
+    scene.add(Cone::new(Vector3::new(-1.0, 0.9, -0.3), Vector3::new(-1.0, 1.0, -0.3), 0.12, 0.0, false, steel.clone()));
+
+    // A washer lying flat, and a frustum standing on it.
+    scene.add(Disk::annulus(Vector3::new(0.0, 0.01, 0.3), Vector3::new(0.0, 1.0, 0.0), 0.15, 0.4, Lambert::new(Vector3::new(0.9, 0.7, 0.2))));
+    scene.add(Cone::new(Vector3::new(0.0, 0.01, 0.3), Vector3::new(0.0, 0.6, 0.3), 0.3, 0.1, true, Lambert::new(Vector3::new(0.2, 0.5, 0.8))));
+
+    // A tube lying on its side with a quarter cut away, so the inside shows, closed off at the back.
+    let mut tube = Cylinder::new(Vector3::new(0.9, 0.35, -0.8), Vector3::new(0.9, 0.35, 0.2), 0.35, false, Lambert::new(Vector3::new(0.8, 0.25, 0.2)));
+    tube.sweep = 270.0;
+    scene.add(tube);
+    scene.add(Disk::new(Vector3::new(0.9, 0.35, -0.8), Vector3::new(0.0, 0.0, -1.0), 0.35, steel));
+
+    let camera_origin = Vector3{x: 0.0, y: 1.6, z: 2.6};
+    let camera_look_at = Vector3{x: 0.0, y: 0.3, z: -0.2};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 55.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
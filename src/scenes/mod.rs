@@ -0,0 +1,62 @@
+pub mod basic;
+pub mod cornell_box;
+pub mod showcase;
+pub mod materials;
+pub mod many_lights;
+pub mod triangle_soup;
+pub mod motion;
+pub mod turntable;
+pub mod mechanical;
+pub mod quartics;
+pub mod csg;
+pub mod sdf;
+pub mod fractals;
+pub mod terrain;
+
+use camera::path::CameraPath;
+use camera::perspective::Perspective;
+use material::Material;
+use renderable::triangle::Triangle;
+use scene::Scene;
+use vector3::Vector3;
+
+pub const NAMES: [&str; 14] = ["basic", "cornell_box", "showcase", "materials", "many_lights", "triangle_soup", "motion", "turntable", "mechanical", "quartics", "csg", "sdf", "fractals", "terrain"];
+
+pub fn build(name: &str, aspect: f64) -> Option<(Scene, Perspective)>
+{
+    match name
+    {
+        "basic" => Some(basic::build(aspect)),
+        "cornell_box" => Some(cornell_box::build(aspect)),
+        "showcase" => Some(showcase::build(aspect)),
+        "materials" => Some(materials::build(aspect)),
+        "many_lights" => Some(many_lights::build(aspect)),
+        "triangle_soup" => Some(triangle_soup::build(aspect)),
+        "motion" => Some(motion::build(aspect)),
+        "turntable" => Some(turntable::build(aspect)),
+        "mechanical" => Some(mechanical::build(aspect)),
+        "quartics" => Some(quartics::build(aspect)),
+        "csg" => Some(csg::build(aspect)),
+        "sdf" => Some(sdf::build(aspect)),
+        "fractals" => Some(fractals::build(aspect)),
+        "terrain" => Some(terrain::build(aspect)),
+        _ => None
+    }
+}
+
+// How the camera moves in scenes that are animated.
+pub fn camera_path(name: &str) -> Option<CameraPath>
+{
+    match name
+    {
+        "turntable" => Some(turntable::camera_path()),
+        _ => None
+    }
+}
+
+// Adds the parallelogram spanned by two edges from a corner, facing the side from which they turn counter-clockwise.
+fn add_quad<T: Material + Clone + 'static>(scene: &mut Scene, corner: Vector3, edge_a: Vector3, edge_b: Vector3, material: T)
+{
+    scene.add(Triangle::new(corner, corner + edge_a, corner + edge_a + edge_b, material.clone()));
+    scene.add(Triangle::new(corner, corner + edge_a + edge_b, corner + edge_b, material));
+}
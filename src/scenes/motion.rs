@@ -0,0 +1,35 @@
+// Things moving between time zero and one, which blur when rendered with the shutter open over that time, as with
+// --shutter-close 1.
+use animation::Track;
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use renderable::cube::Cube;
+use renderable::keyframed::Keyframed;
+use scene::Scene;
+use transform::Transform;
+use vector3::{ZERO, Vector3};
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+    scene.add(Sphere::new(Vector3::new(0.0, 0.4, -1.0), 0.4, Metal::new(Vector3::new(0.8, 0.8, 0.8), 0.1)));
+    scene.add(Sphere::moving(Vector3::new(-1.6, 0.3, 0.0), Vector3::new(-0.6, 0.3, 0.0), 0.3, Lambert::new(Vector3::new(0.8, 0.2, 0.2))));
+    scene.add(Cube::moving(Vector3::new(0.2, 0.25, 0.4), Vector3::new(0.2, 0.85, 0.4), 0.3, 0.5, 0.3, Lambert::new(Vector3::new(0.2, 0.6, 0.8))));
+    scene.add(Keyframed::new(Cube::new(ZERO, 0.6, 0.1, 0.6, Lambert::new(Vector3::new(0.9, 0.7, 0.2))), Track::linear(vec![
+        (0.0, Transform::new(Vector3::new(1.2, 0.5, -0.2), ZERO, 1.0)),
+        (1.0, Transform::new(Vector3::new(1.2, 0.5, -0.2), Vector3::new(0.0, 90.0, 60.0), 1.0))
+    ])));
+
+    let camera_origin = Vector3{x: 0.0, y: 1.2, z: 2.5};
+    let camera_look_at = Vector3{x: 0.0, y: 0.4, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 60.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
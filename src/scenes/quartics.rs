@@ -0,0 +1,33 @@
+// Surfaces that need a quartic solved to hit them: a pair of linked tori and a cube with rounded edges.
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::Aabb;
+use renderable::plane::Plane;
+use renderable::quartic::{Quartic, Term};
+use renderable::torus::Torus;
+use scene::Scene;
+use vector3::Vector3;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, -0.5, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    scene.add(Torus::new(Vector3::new(-1.5, -0.3, 0.0), Vector3::new(0.0, 1.0, 0.0), 0.5, 0.18, Metal::new(Vector3::new(0.9, 0.7, 0.3), 0.1)));
+    scene.add(Torus::new(Vector3::new(-1.0, -0.1, 0.0), Vector3::new(0.0, 0.0, 1.0), 0.45, 0.12, Lambert::new(Vector3::new(0.2, 0.5, 0.8))));
+
+    // x⁴ + y⁴ + z⁴ = 0.5⁴ around the origin.
+    let terms = vec![Term::new(1.0, 4, 0, 0), Term::new(1.0, 0, 4, 0), Term::new(1.0, 0, 0, 4), Term::new(-0.0625, 0, 0, 0)];
+    let bounds = Aabb::new(Vector3::new(-0.51, -0.51, -0.51), Vector3::new(0.51, 0.51, 0.51));
+    scene.add(Quartic::new(terms, bounds, Lambert::new(Vector3::new(0.8, 0.25, 0.2))));
+
+    let camera_origin = Vector3{x: 0.0, y: 1.2, z: 2.8};
+    let camera_look_at = Vector3{x: -0.5, y: -0.1, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 50.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
@@ -0,0 +1,63 @@
+// Shapes modelled as signed distance fields: blobs melted together, a rounded box with a scoop smoothly carved out, a
+// twisted column, a rippled ball and a row of capsules from a single repeated one.
+use camera::perspective::Perspective;
+use distance::Smooth;
+use distance::domain::{Displace, Repeat, Transformed, Twist};
+use distance::shapes::{Capsule, Cuboid, Sphere, Torus};
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::Aabb;
+use renderable::csg::Operation;
+use renderable::plane::Plane;
+use renderable::sdf::Sdf;
+use scene::Scene;
+use transform::Transform;
+use vector3::{ZERO, Vector3};
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    let blobs = Smooth::new(Operation::Union,
+        Smooth::new(Operation::Union, Sphere::new(Vector3::new(-1.1, 0.3, 0.0), 0.25), Sphere::new(Vector3::new(-1.1, 0.65, 0.0), 0.13), 0.15),
+        Torus::new(Vector3::new(-1.1, 0.1, 0.0), 0.4, 0.1), 0.15);
+    let mut blobs = Sdf::new(blobs, Lambert::new(Vector3::new(0.2, 0.5, 0.8)));
+    blobs.bounds = Some(Aabb::new(Vector3::new(-1.65, 0.0, -0.55), Vector3::new(-0.55, 0.8, 0.55)));
+    scene.add(blobs);
+
+    // Modelled around the origin, then turned and put in place.
+    let scooped = Smooth::new(Operation::Difference,
+        Cuboid::rounded(ZERO, Vector3::new(0.6, 0.6, 0.6), 0.08),
+        Sphere::new(Vector3::new(0.0, 0.35, 0.0), 0.3), 0.05);
+    let placed = Transformed::new(scooped, Transform::new(Vector3::new(-0.3, 0.3, -0.5), Vector3::new(0.0, 30.0, 0.0), 1.0));
+    let mut scooped = Sdf::new(placed, Lambert::new(Vector3::new(0.8, 0.25, 0.2)));
+    scooped.bounds = Some(Aabb::new(Vector3::new(-0.75, 0.0, -0.95), Vector3::new(0.15, 0.6, -0.05)));
+    scene.add(scooped);
+
+    // Twisting overestimates distances, so it's traced with shorter steps.
+    let mut column = Sdf::new(Twist::new(Cuboid::new(Vector3::new(0.0, 0.5, 0.0), Vector3::new(0.25, 1.0, 0.25)), 90.0), Metal::new(Vector3::new(0.9, 0.7, 0.3), 0.2));
+    column.step = 0.6;
+    column.bounds = Some(Aabb::new(Vector3::new(-0.2, 0.0, -0.2), Vector3::new(0.2, 1.0, 0.2)));
+    scene.add(column);
+
+    let mut rippled = Sdf::new(Displace::new(Sphere::new(Vector3::new(0.8, 0.35, 0.1), 0.3), 0.02, 6.0), Lambert::new(Vector3::new(0.3, 0.7, 0.3)));
+    rippled.step = 0.5;
+    rippled.bounds = Some(Aabb::new(Vector3::new(0.45, 0.0, -0.25), Vector3::new(1.15, 0.7, 0.45)));
+    scene.add(rippled);
+
+    // Capsules repeated along x, cut down to five of them by the bounds.
+    let capsule = Capsule::new(Vector3::new(0.0, 0.08, 0.45), Vector3::new(0.0, 0.08, 0.85), 0.08);
+    let mut row = Sdf::new(Repeat::new(capsule, Vector3::new(0.3, 0.0, 0.0)), Lambert::new(Vector3::new(0.9, 0.9, 0.9)));
+    row.bounds = Some(Aabb::new(Vector3::new(-0.75, 0.0, 0.35), Vector3::new(0.75, 0.16, 0.95)));
+    scene.add(row);
+
+    let camera_origin = Vector3{x: 0.0, y: 1.3, z: 2.6};
+    let camera_look_at = Vector3{x: -0.2, y: 0.3, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 55.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
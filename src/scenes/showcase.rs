@@ -0,0 +1,55 @@
+use rand::{SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use material::dielectric::Dielectric;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use scene::Scene;
+use vector3::Vector3;
+
+// A field of small randomly placed spheres around three large ones, one of each material.
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut rng = SmallRng::seed_from_u64(1);
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    for a in -11..11
+    {
+        for b in -11..11
+        {
+            let center = Vector3::new(a as f64 + 0.9 * rng.random::<f64>(), 0.2, b as f64 + 0.9 * rng.random::<f64>());
+            if (center - Vector3::new(4.0, 0.2, 0.0)).length() <= 0.9
+            {
+                continue;
+            }
+
+            let choice = rng.random::<f64>();
+            let color = Vector3::new(rng.random::<f64>(), rng.random::<f64>(), rng.random::<f64>());
+            if choice < 0.8
+            {
+                scene.add(Sphere::new(center, 0.2, Lambert::new(color * color)));
+            }
+            else if choice < 0.95
+            {
+                scene.add(Sphere::new(center, 0.2, Metal::new(0.5 * (color + Vector3::new(1.0, 1.0, 1.0)), 0.5 * rng.random::<f64>())));
+            }
+            else
+            {
+                scene.add(Sphere::new(center, 0.2, Dielectric::new(1.5)));
+            }
+        }
+    }
+
+    scene.add(Sphere::new(Vector3::new(0.0, 1.0, 0.0), 1.0, Dielectric::new(1.5)));
+    scene.add(Sphere::new(Vector3::new(-4.0, 1.0, 0.0), 1.0, Lambert::new(Vector3::new(0.4, 0.2, 0.1))));
+    scene.add(Sphere::new(Vector3::new(4.0, 1.0, 0.0), 1.0, Metal::new(Vector3::new(0.7, 0.6, 0.5), 0.0)));
+
+    let camera = Perspective::new(
+        Vector3::new(13.0, 2.0, 3.0), Vector3::new(0.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0), 20.0, aspect, 0.1, 10.0);
+
+    (scene, camera)
+}
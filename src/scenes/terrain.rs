@@ -0,0 +1,53 @@
+// Rolling hills from a grid of heights, with a lake filling the low ground between them.
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::heightfield::Heightfield;
+use renderable::plane_bounded::PlaneBounded;
+use scene::Scene;
+use vector3::Vector3;
+
+const SAMPLES: usize = 257;
+const ORIGIN: Vector3 = Vector3{x: -3.0, y: 0.0, z: -3.0};
+const SIZE: Vector3 = Vector3{x: 6.0, y: 1.0, z: 6.0};
+
+// Waves of a few sizes piled on each other, a common stand in for a height map from a file.
+fn height(x: f64, z: f64) -> f64
+{
+    let hills = (x * 1.7).sin() * (z * 1.3).cos() + 0.5 * (x * 3.1 + z * 2.3).sin() + 0.25 * (x * 7.3 - z * 6.1).cos() * (z * 5.7).sin();
+    ((hills + 1.75) / 3.5).clamp(0.0, 1.0)
+}
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let step = SIZE.x / (SAMPLES - 1) as f64;
+    let heights = (0..SAMPLES * SAMPLES).map(|n| height((n % SAMPLES) as f64 * step, (n / SAMPLES) as f64 * step)).collect();
+    build_around(aspect, Heightfield::new(SAMPLES, SAMPLES, heights, ORIGIN, SIZE, ground()))
+}
+
+// The same scene with hills from an image, stretched over the same ground whatever its size.
+pub fn build_with_heightmap(aspect: f64, path: &str) -> Result<(Scene, Perspective), String>
+{
+    Heightfield::load(path, ORIGIN, SIZE, ground()).map(|field| build_around(aspect, field))
+}
+
+fn ground() -> Lambert
+{
+    Lambert::new(Vector3::new(0.45, 0.55, 0.3))
+}
+
+fn build_around(aspect: f64, field: Heightfield) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(field);
+    scene.add(PlaneBounded::new(Vector3::new(0.0, 0.35, 0.0), Vector3::new(0.0, 1.0, 0.0), SIZE.x, SIZE.z, Metal::new(Vector3::new(0.5, 0.6, 0.7), 0.05)));
+
+    let camera_origin = Vector3{x: 0.0, y: 2.2, z: 4.0};
+    let camera_look_at = Vector3{x: 0.0, y: 0.3, z: 0.0};
+    let camera_up = Vector3{x: 0.0, y: 1.0, z: 0.0};
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        camera_up, 55.0, aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
@@ -0,0 +1,46 @@
+use rand::{SeedableRng, RngExt};
+use rand::rngs::SmallRng;
+use camera::perspective::Perspective;
+use material::lambert::Lambert;
+use renderable::plane::Plane;
+use renderable::triangle::Triangle;
+use scene::Scene;
+use vector3::Vector3;
+
+pub const TRIANGLE_COUNT: usize = 2000;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    build_with_count(aspect, TRIANGLE_COUNT)
+}
+
+// Small, randomly oriented triangles scattered through a cube, wound to face the camera.
+pub fn build_with_count(aspect: f64, count: usize) -> (Scene, Perspective)
+{
+    let camera_origin = Vector3::new(0.0, 1.5, 6.0);
+    let mut rng = SmallRng::seed_from_u64(1);
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.5, 0.5, 0.5))));
+
+    let mut random_vector = |scale: f64| Vector3::new(rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5, rng.random::<f64>() - 0.5) * scale;
+    for _ in 0..count
+    {
+        let center = random_vector(3.0) + Vector3::new(0.0, 1.6, 0.0);
+        let a = center + random_vector(0.4);
+        let mut b = center + random_vector(0.4);
+        let mut c = center + random_vector(0.4);
+        if (b - a).cross(c - a).dot(camera_origin - a) < 0.0
+        {
+            ::std::mem::swap(&mut b, &mut c);
+        }
+
+        let color = random_vector(0.6) + Vector3::new(0.5, 0.5, 0.5);
+        scene.add(Triangle::new(a, b, c, Lambert::new(color)));
+    }
+
+    let camera = Perspective::new(
+        camera_origin, Vector3::new(0.0, 1.5, 0.0),
+        Vector3::new(0.0, 1.0, 0.0), 50.0, aspect, 0.0, 1.0);
+
+    (scene, camera)
+}
@@ -0,0 +1,59 @@
+// A few things on a pedestal with the camera circling them once over four seconds, for rendering as a sequence of
+// frames with --frames.
+use std::f64::consts::PI;
+use animation::{Interpolation, Key, Track, EASE_IN_OUT};
+use camera::path::CameraPath;
+use camera::perspective::Perspective;
+use material::animated::Animated;
+use material::lambert::Lambert;
+use material::metal::Metal;
+use renderable::plane::Plane;
+use renderable::sphere::Sphere;
+use renderable::cube::Cube;
+use renderable::keyframed::Keyframed;
+use scene::Scene;
+use transform::Transform;
+use vector3::{ZERO, ONE, Vector3};
+
+const SECONDS: f64 = 4.0;
+const RADIUS: f64 = 3.0;
+const HEIGHT: f64 = 1.4;
+
+pub fn build(aspect: f64) -> (Scene, Perspective)
+{
+    let mut scene = Scene::new();
+    scene.add(Plane::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Lambert::new(Vector3::new(0.6, 0.6, 0.6))));
+    scene.add(Cube::new(Vector3::new(0.0, 0.1, 0.0), 2.4, 0.2, 2.4, Lambert::new(Vector3::new(0.3, 0.3, 0.35))));
+    scene.add(Sphere::new(Vector3::new(-0.5, 0.6, -0.3), 0.4, Metal::new(Vector3::new(0.9, 0.8, 0.6), 0.05)));
+
+    // The sphere's color eases from red to green to blue and back.
+    let colors = [Vector3::new(0.8, 0.1, 0.1), Vector3::new(0.1, 0.8, 0.1), Vector3::new(0.1, 0.1, 0.8), Vector3::new(0.8, 0.1, 0.1)];
+    let color = Track::new(colors.iter().enumerate().map(|(i, &c)| Key::new(i as f64 * SECONDS / 3.0, c, EASE_IN_OUT)).collect());
+    scene.add(Sphere::new(Vector3::new(0.6, 0.5, 0.4), 0.3, Animated::new(Lambert::new(ONE), color)));
+
+    // The cube turns in quarter turns, holding still in between.
+    let turns = (0..5).map(|i| Key::new(i as f64 * SECONDS / 4.0, Transform::new(Vector3::new(0.4, 0.45, -0.6), Vector3::new(0.0, 90.0 * i as f64, 0.0), 1.0), Interpolation::Step)).collect();
+    scene.add(Keyframed::new(Cube::new(ZERO, 0.5, 0.5, 0.5, Lambert::new(Vector3::new(0.9, 0.6, 0.1))), Track::new(turns)));
+
+    let path = camera_path();
+    let (camera_origin, camera_look_at) = (path.origin.value_at(0.0), path.look_at.value_at(0.0));
+    let camera = Perspective::new(
+        camera_origin, camera_look_at,
+        path.up, path.fov.value_at(0.0), aspect, 0.0, (camera_origin - camera_look_at).length());
+
+    (scene, camera)
+}
+
+pub fn camera_path() -> CameraPath
+{
+    // Enough keys around the circle that the straight lines between them are close to it.
+    let steps = 48;
+    let origin = Track::linear((0..steps + 1).map(|i|
+    {
+        let angle = 2.0 * PI * i as f64 / steps as f64;
+        (SECONDS * i as f64 / steps as f64, Vector3::new(RADIUS * angle.sin(), HEIGHT, RADIUS * angle.cos()))
+    }).collect());
+    // Zooming in for the first half and back out for the second.
+    let fov = Track::new(vec![Key::new(0.0, 50.0, EASE_IN_OUT), Key::new(SECONDS / 2.0, 35.0, EASE_IN_OUT), Key::new(SECONDS, 50.0, EASE_IN_OUT)]);
+    CameraPath::new(origin, Track::constant(Vector3::new(0.0, 0.4, 0.0)), fov, Vector3::new(0.0, 1.0, 0.0))
+}
@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, Write};
+use std::time::Duration;
+
+// Counters are kept per thread while rendering, so that collecting them never needs a lock, and are summed up once
+// each render thread is done.
+#[derive(Clone, Default)]
+pub struct Counters
+{
+    pub camera_rays: u64,
+    // Every ray traced through the scene, whether it continues a path or is a shadow ray.
+    pub rays: u64,
+    // Nothing traces shadow rays yet, so this stays at zero until light sampling does.
+    pub shadow_rays: u64,
+    // Tests of a ray against a renderable, by the kind of renderable.
+    pub intersection_tests: Vec<(&'static str, u64)>,
+    pub paths_at_bounce_max: u64,
+    pub non_finite_samples: u64
+}
+
+impl Counters
+{
+    pub fn add_intersection_tests(&mut self, kind: &'static str, count: u64)
+    {
+        match self.intersection_tests.iter_mut().find(|&&mut (k, _)| k == kind)
+        {
+            Some(entry) => entry.1 += count,
+            None => self.intersection_tests.push((kind, count))
+        }
+    }
+
+    pub fn merge(&mut self, other: &Counters)
+    {
+        self.camera_rays += other.camera_rays;
+        self.rays += other.rays;
+        self.shadow_rays += other.shadow_rays;
+        for &(kind, count) in other.intersection_tests.iter()
+        {
+            self.add_intersection_tests(kind, count);
+        }
+        self.paths_at_bounce_max += other.paths_at_bounce_max;
+        self.non_finite_samples += other.non_finite_samples;
+    }
+
+    // Segments per camera ray, counting the one leaving the camera. Every ray that isn't a shadow ray is a segment.
+    pub fn average_path_length(&self) -> f64
+    {
+        if self.camera_rays == 0 { 0.0 } else { (self.rays - self.shadow_rays) as f64 / self.camera_rays as f64 }
+    }
+}
+
+thread_local!(static COUNTERS: RefCell<Counters> = RefCell::new(Counters::default()));
+
+pub fn record<F: FnOnce(&mut Counters)>(f: F)
+{
+    COUNTERS.with(|c| f(&mut c.borrow_mut()))
+}
+
+// Returns this thread's counters and starts it counting from zero again.
+pub fn take() -> Counters
+{
+    COUNTERS.with(|c| c.replace(Counters::default()))
+}
+
+pub struct Report
+{
+    pub counters: Counters,
+    pub phases: Vec<(&'static str, Duration)>
+}
+
+impl Report
+{
+    pub fn new() -> Report
+    {
+        Report { counters: Counters::default(), phases: Vec::new() }
+    }
+
+    pub fn print(&self)
+    {
+        let c = &self.counters;
+        println!("Camera rays:          {}", c.camera_rays);
+        println!("Total rays:           {}", c.rays);
+        println!("Shadow rays:          {}", c.shadow_rays);
+        println!("Intersection tests:");
+        for &(kind, count) in c.intersection_tests.iter()
+        {
+            println!("  {:<20}{}", kind, count);
+        }
+        println!("Average path length:  {:.3}", c.average_path_length());
+        println!("Paths at bounce max:  {}", c.paths_at_bounce_max);
+        println!("Non-finite samples:   {}", c.non_finite_samples);
+        println!("Wall time:");
+        for &(phase, duration) in self.phases.iter()
+        {
+            println!("  {:<20}{:.3}s", phase, duration.as_secs_f64());
+        }
+        let total: Duration = self.phases.iter().map(|&(_, d)| d).sum();
+        println!("  {:<20}{:.3}s", "total", total.as_secs_f64());
+    }
+
+    pub fn write_json(&self, path: &str) -> io::Result<()>
+    {
+        let c = &self.counters;
+        let object = |entries: Vec<String>| format!("{{ {} }}", entries.join(", "));
+        let tests = c.intersection_tests.iter().map(|&(kind, count)| format!("\"{}\": {}", kind, count)).collect();
+        let phases = self.phases.iter().map(|&(phase, d)| format!("\"{}\": {:.6}", phase, d.as_secs_f64())).collect();
+
+        let mut file = File::create(path)?;
+        writeln!(file, "{{")?;
+        writeln!(file, "  \"camera_rays\": {},", c.camera_rays)?;
+        writeln!(file, "  \"rays\": {},", c.rays)?;
+        writeln!(file, "  \"shadow_rays\": {},", c.shadow_rays)?;
+        writeln!(file, "  \"intersection_tests\": {},", object(tests))?;
+        writeln!(file, "  \"average_path_length\": {:.6},", c.average_path_length())?;
+        writeln!(file, "  \"paths_at_bounce_max\": {},", c.paths_at_bounce_max)?;
+        writeln!(file, "  \"non_finite_samples\": {},", c.non_finite_samples)?;
+        writeln!(file, "  \"seconds\": {}", object(phases))?;
+        writeln!(file, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use filter;
+    use renderer::Renderer;
+    use scenes;
+
+    #[test]
+    fn counters_add_up_over_a_render()
+    {
+        let (scene, camera) = scenes::build("basic", 2.0).unwrap();
+        let mut renderer = Renderer::new(16, 8, 3, 4);
+        renderer.seed = 1;
+        let c = renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).counters;
+
+        assert_eq!(c.camera_rays, 16 * 8 * 3);
+        assert_eq!(c.shadow_rays, 0);
+        assert!(c.average_path_length() >= 1.0 && c.average_path_length() <= 6.0);
+        // The basic scene has two spheres, two cubes, a plane and a bounded plane. Only the plane has no bounds to
+        // skip it with, so it's the only one tested against every ray.
+        let tests = |kind: &str| c.intersection_tests.iter().find(|&&(k, _)| k == kind).map_or(0, |&(_, n)| n);
+        assert!(tests("sphere") > 0 && tests("sphere") < 2 * c.rays);
+        assert!(tests("cube") > 0 && tests("cube") < 2 * c.rays);
+        assert_eq!(tests("plane"), c.rays);
+        assert!(tests("plane_bounded") > 0 && tests("plane_bounded") < c.rays);
+        assert_eq!(c.non_finite_samples, 0);
+    }
+
+    #[test]
+    fn aovs_are_taken_from_the_paths_already_traced()
+    {
+        let (scene, camera) = scenes::build("basic", 2.0).unwrap();
+        let render = |aovs: bool|
+        {
+            let mut renderer = Renderer::new(16, 8, 2, 4);
+            renderer.seed = 1;
+            renderer.aovs = aovs;
+            renderer.render(&scene, &camera, filter::from_name("box", None).unwrap()).counters
+        };
+        let (without, with) = (render(false), render(true));
+        assert_eq!(without.rays, with.rays);
+        assert_eq!(without.intersection_tests, with.intersection_tests);
+    }
+}
@@ -0,0 +1,89 @@
+use animation::Animatable;
+use vector3::Vector3;
+
+// Places something in the scene by scaling it evenly, rotating it about the x, y and z axes in that order, then
+// moving it. Rotations are in degrees.
+#[derive(Clone, Copy)]
+pub struct Transform
+{
+    pub translation: Vector3,
+    pub rotation: Vector3,
+    pub scale: f64
+}
+
+fn rotate_axis(v: Vector3, degrees: f64, axis: usize) -> Vector3
+{
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    match axis
+    {
+        0 => Vector3::new(v.x, v.y * cos - v.z * sin, v.y * sin + v.z * cos),
+        1 => Vector3::new(v.x * cos + v.z * sin, v.y, -v.x * sin + v.z * cos),
+        _ => Vector3::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos, v.z)
+    }
+}
+
+impl Transform
+{
+    pub fn new(translation: Vector3, rotation: Vector3, scale: f64) -> Transform
+    {
+        Transform { translation: translation, rotation: rotation, scale: scale }
+    }
+
+    pub fn rotate(&self, v: Vector3) -> Vector3
+    {
+        let v = rotate_axis(v, self.rotation.x, 0);
+        let v = rotate_axis(v, self.rotation.y, 1);
+        rotate_axis(v, self.rotation.z, 2)
+    }
+
+    pub fn unrotate(&self, v: Vector3) -> Vector3
+    {
+        let v = rotate_axis(v, -self.rotation.z, 2);
+        let v = rotate_axis(v, -self.rotation.y, 1);
+        rotate_axis(v, -self.rotation.x, 0)
+    }
+
+    pub fn inverse_point(&self, p: Vector3) -> Vector3
+    {
+        self.unrotate(p - self.translation) / self.scale
+    }
+
+    pub fn inverse_vector(&self, v: Vector3) -> Vector3
+    {
+        self.unrotate(v) / self.scale
+    }
+}
+
+impl Animatable for Transform
+{
+    // Blends each part separately, so rotations turn about each axis at their own steady rate.
+    fn lerp(&self, other: &Transform, f: f64) -> Transform
+    {
+        Transform
+        {
+            translation: self.translation + (other.translation - self.translation) * f,
+            rotation: self.rotation + (other.rotation - self.rotation) * f,
+            scale: self.scale + (other.scale - self.scale) * f
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use transform::Transform;
+    use vector3::{ZERO, Vector3};
+
+    #[test]
+    fn inverse_undoes_the_transform()
+    {
+        let transform = Transform::new(Vector3::new(1.0, -2.0, 3.0), Vector3::new(30.0, 45.0, -60.0), 2.5);
+        let p = Vector3::new(0.3, 0.7, -1.1);
+        let moved = transform.rotate(p * transform.scale) + transform.translation;
+        assert!((transform.inverse_point(moved) - p).length() < 1e-12);
+        assert!((transform.unrotate(transform.rotate(p)) - p).length() < 1e-12);
+        // A quarter turn about y takes x to -z.
+        let turn = Transform::new(ZERO, Vector3::new(0.0, 90.0, 0.0), 1.0);
+        assert!((turn.rotate(Vector3::new(1.0, 0.0, 0.0)) - Vector3::new(0.0, 0.0, -1.0)).length() < 1e-12);
+    }
+}
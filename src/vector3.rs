@@ -47,6 +47,11 @@ impl Vector3
     {
         *self / self.length()
     }
+
+    pub fn is_finite(&self) -> bool
+    {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
 }
 
 impl Neg for Vector3
@@ -0,0 +1,161 @@
+use std::fs::File;
+use std::io::{self, Write, Seek, SeekFrom, BufWriter};
+use lodepng::{ColorType, Encoder};
+use rgb::RGBA;
+use video::{VideoWriter, VideoOptions, frame_rate_fraction};
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub fn crc32(bytes: &[u8]) -> u32
+{
+    let mut crc = !0u32;
+    for &byte in bytes
+    {
+        crc ^= byte as u32;
+        for _ in 0..8
+        {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8>
+{
+    let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(kind);
+    chunk.extend_from_slice(data);
+    let crc = crc32(&chunk[4..]);
+    chunk.extend_from_slice(&crc.to_be_bytes());
+    chunk
+}
+
+// Splits a PNG file into its chunks' types and data.
+fn chunks(png: &[u8]) -> Vec<([u8; 4], &[u8])>
+{
+    let mut chunks = Vec::new();
+    let mut offset = SIGNATURE.len();
+    while offset + 12 <= png.len()
+    {
+        let length = u32::from_be_bytes([png[offset], png[offset + 1], png[offset + 2], png[offset + 3]]) as usize;
+        let kind = [png[offset + 4], png[offset + 5], png[offset + 6], png[offset + 7]];
+        chunks.push((kind, &png[offset + 8..offset + 8 + length]));
+        offset += length + 12;
+    }
+    chunks
+}
+
+// How long each frame lasts as a fraction of a second, which APNG holds in 16 bits each. Exact frame rates that don't
+// fit even in lowest terms are rounded to the nearest millisecond.
+pub fn frame_delay(fps: f64) -> (u16, u16)
+{
+    let (rate, scale) = frame_rate_fraction(fps);
+    let (mut a, mut b) = (rate, scale);
+    while b != 0
+    {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    let (numerator, denominator) = (scale / a, rate / a);
+    if numerator <= u16::MAX as u32 && denominator <= u16::MAX as u32
+    {
+        (numerator as u16, denominator as u16)
+    }
+    else
+    {
+        ((1000.0 / fps).round().clamp(1.0, u16::MAX as f64) as u16, 1000)
+    }
+}
+
+// An animated PNG, with each frame compressed by lodepng as a whole RGBA image and its data moved into the frame
+// chunks APNG adds. Players that don't know APNG show the first frame.
+pub struct ApngWriter
+{
+    file: BufWriter<File>,
+    encoder: Encoder,
+    width: usize,
+    height: usize,
+    options: VideoOptions,
+    // Where the animation control chunk is, to fill in the frame count at the end.
+    control_offset: u64,
+    frames: u32,
+    sequence: u32
+}
+
+impl ApngWriter
+{
+    pub fn create(path: &str, width: usize, height: usize, options: &VideoOptions) -> io::Result<ApngWriter>
+    {
+        // Every frame has to be stored the same way as the first, so lodepng mustn't pick a format per frame.
+        let mut encoder = Encoder::new();
+        encoder.set_auto_convert(false);
+        encoder.info_raw_mut().colortype = ColorType::RGBA;
+        encoder.info_png_mut().color.colortype = ColorType::RGBA;
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(SIGNATURE)?;
+        let mut header = (width as u32).to_be_bytes().to_vec();
+        header.extend_from_slice(&(height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+        file.write_all(&chunk(b"IHDR", &header))?;
+        let control_offset = file.stream_position()?;
+        file.write_all(&chunk(b"acTL", &[0; 8]))?;
+        Ok(ApngWriter { file: file, encoder: encoder, width: width, height: height, options: options.clone(), control_offset: control_offset, frames: 0, sequence: 0 })
+    }
+
+    fn control_data(&self) -> Vec<u8>
+    {
+        let mut data = self.frames.to_be_bytes().to_vec();
+        data.extend_from_slice(&self.options.loops.to_be_bytes());
+        data
+    }
+}
+
+impl VideoWriter for ApngWriter
+{
+    fn write_frame(&mut self, pixels: &[RGBA<u8>]) -> io::Result<()>
+    {
+        let png = self.encoder.encode(pixels, self.width, self.height).map_err(|e| io::Error::other(e.to_string()))?;
+
+        // Frames cover the whole image, last 1/fps seconds, and replace what was there.
+        let (numerator, denominator) = frame_delay(self.options.fps);
+        let mut control = self.sequence.to_be_bytes().to_vec();
+        for value in [self.width as u32, self.height as u32, 0, 0].iter()
+        {
+            control.extend_from_slice(&value.to_be_bytes());
+        }
+        control.extend_from_slice(&numerator.to_be_bytes());
+        control.extend_from_slice(&denominator.to_be_bytes());
+        control.extend_from_slice(&[0, 0]);
+        self.file.write_all(&chunk(b"fcTL", &control))?;
+        self.sequence += 1;
+
+        // The first frame is also the image shown without animation, so it keeps its image data chunks.
+        for (_, data) in chunks(&png).into_iter().filter(|&(kind, _)| &kind == b"IDAT")
+        {
+            if self.frames == 0
+            {
+                self.file.write_all(&chunk(b"IDAT", data))?;
+            }
+            else
+            {
+                let mut frame_data = self.sequence.to_be_bytes().to_vec();
+                frame_data.extend_from_slice(data);
+                self.file.write_all(&chunk(b"fdAT", &frame_data))?;
+                self.sequence += 1;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()>
+    {
+        self.file.write_all(&chunk(b"IEND", &[]))?;
+        self.file.seek(SeekFrom::Start(self.control_offset))?;
+        let control = chunk(b"acTL", &self.control_data());
+        self.file.write_all(&control)?;
+        self.file.flush()
+    }
+}
@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::convert::TryFrom;
+use std::io::{self, Write, Seek, SeekFrom, BufWriter};
+use rgb::RGBA;
+use video::{VideoWriter, frame_rate_fraction};
+
+const AVIF_HASINDEX: u32 = 0x10;
+const AVIIF_KEYFRAME: u32 = 0x10;
+
+// Where the sizes and counts only known once every frame is written are, from the start of the file.
+const RIFF_SIZE_OFFSET: u64 = 4;
+const TOTAL_FRAMES_OFFSET: u64 = 48;
+const STREAM_LENGTH_OFFSET: u64 = 140;
+const MOVI_SIZE_OFFSET: u64 = 216;
+const MOVI_OFFSET: u64 = 220;
+
+fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8>
+{
+    let mut chunk = id.to_vec();
+    chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(data);
+    chunk
+}
+
+fn list(kind: &[u8; 4], contents: &[u8]) -> Vec<u8>
+{
+    let mut data = kind.to_vec();
+    data.extend_from_slice(contents);
+    chunk(b"LIST", &data)
+}
+
+// Sizes and offsets are 32 bits, which is as far as an AVI 1.0 file can reach.
+fn size(value: u64) -> io::Result<u32>
+{
+    u32::try_from(value).map_err(|_| io::Error::other("AVI 1.0 files can't be larger than 4 GiB"))
+}
+
+fn words(values: &[u32]) -> Vec<u8>
+{
+    values.iter().flat_map(|v| v.to_le_bytes().to_vec()).collect()
+}
+
+// An AVI 1.0 file holding a single stream of uncompressed 24-bit frames, which every player can show without a codec.
+// Frames are stored bottom up in BGR order, with each row padded to four bytes.
+pub struct AviWriter
+{
+    file: BufWriter<File>,
+    width: usize,
+    height: usize,
+    frame_size: usize,
+    // Each frame's offset from the movi list, for the index.
+    offsets: Vec<u32>
+}
+
+impl AviWriter
+{
+    pub fn create(path: &str, width: usize, height: usize, fps: f64) -> io::Result<AviWriter>
+    {
+        let (rate, scale) = frame_rate_fraction(fps);
+        let frame_size = (width * 3).div_ceil(4) * 4 * height;
+        let (w, h, size) = (width as u32, height as u32, size(frame_size as u64)?);
+
+        let main_header = words(&[(1e6 / fps).round() as u32, (frame_size as f64 * fps).ceil() as u32, 0, AVIF_HASINDEX, 0, 0, 1, size, w, h, 0, 0, 0, 0]);
+        let mut stream_header = b"vidsDIB ".to_vec();
+        stream_header.extend(words(&[0, 0, 0, scale, rate, 0, 0, size, u32::MAX, 0]));
+        stream_header.extend([0u16, 0, w as u16, h as u16].iter().flat_map(|v| v.to_le_bytes().to_vec()));
+        // A positive height in the bitmap header means the rows are bottom up.
+        let mut format = words(&[40, w, h]);
+        format.extend_from_slice(&1u16.to_le_bytes());
+        format.extend_from_slice(&24u16.to_le_bytes());
+        format.extend(words(&[0, size, 0, 0, 0, 0]));
+
+        let stream_list = list(b"strl", &[chunk(b"strh", &stream_header), chunk(b"strf", &format)].concat());
+        let header_list = list(b"hdrl", &[chunk(b"avih", &main_header), stream_list].concat());
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(b"RIFF\0\0\0\0AVI ")?;
+        file.write_all(&header_list)?;
+        file.write_all(b"LIST\0\0\0\0movi")?;
+        Ok(AviWriter { file: file, width: width, height: height, frame_size: frame_size, offsets: Vec::new() })
+    }
+}
+
+impl VideoWriter for AviWriter
+{
+    fn write_frame(&mut self, pixels: &[RGBA<u8>]) -> io::Result<()>
+    {
+        let stride = self.frame_size / self.height;
+        let mut data = vec![0u8; self.frame_size];
+        for y in 0..self.height
+        {
+            let row = &mut data[(self.height - 1 - y) * stride..];
+            for x in 0..self.width
+            {
+                let p = pixels[y * self.width + x];
+                row[x * 3..x * 3 + 3].copy_from_slice(&[p.b, p.g, p.r]);
+            }
+        }
+
+        // Frames that wouldn't leave room for the index are refused before any of them is written.
+        let position = self.file.stream_position()?;
+        let index_size = 8 + 16 * (self.offsets.len() as u64 + 1);
+        size(position + 8 + self.frame_size as u64 + index_size - 8)?;
+        self.offsets.push(size(position - MOVI_OFFSET)?);
+        self.file.write_all(&chunk(b"00db", &data))
+    }
+
+    fn finish(&mut self) -> io::Result<()>
+    {
+        let movi_end = self.file.stream_position()?;
+        let index: Vec<u8> = self.offsets.iter().flat_map(|&offset|
+        {
+            let mut entry = b"00db".to_vec();
+            entry.extend(words(&[AVIIF_KEYFRAME, offset, self.frame_size as u32]));
+            entry
+        }).collect();
+        self.file.write_all(&chunk(b"idx1", &index))?;
+        let end = self.file.stream_position()?;
+
+        let frames = self.offsets.len() as u32;
+        for &(offset, value) in [(RIFF_SIZE_OFFSET, size(end - 8)?), (TOTAL_FRAMES_OFFSET, frames), (STREAM_LENGTH_OFFSET, frames),
+            (MOVI_SIZE_OFFSET, size(movi_end - MOVI_SIZE_OFFSET - 4)?)].iter()
+        {
+            self.file.seek(SeekFrom::Start(offset))?;
+            self.file.write_all(&value.to_le_bytes())?;
+        }
+        self.file.flush()
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, Write, BufWriter};
+use rgb::RGBA;
+use video::{VideoWriter, VideoOptions};
+use video::quantize;
+
+const MAX_CODE: u16 = 4095;
+
+// Packs codes of varying width into bytes, least significant bit first.
+struct BitWriter
+{
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32
+}
+
+impl BitWriter
+{
+    fn write(&mut self, code: u16, width: u32)
+    {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+        while self.bits >= 8
+        {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8>
+    {
+        if self.bits > 0
+        {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// Compresses palette indices the way GIF does: LZW with codes growing from nine bits up to twelve, after which the
+// table is cleared and starts over.
+pub fn lzw(indices: &[u8]) -> Vec<u8>
+{
+    let (clear, end) = (256, 257);
+    let mut output = BitWriter { bytes: Vec::new(), buffer: 0, bits: 0 };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = 9;
+
+    output.write(clear, width);
+    let mut current = match indices.first() { Some(&i) => i as u16, None => { output.write(end, width); return output.finish(); } };
+    for &index in indices[1..].iter()
+    {
+        if let Some(&code) = table.get(&(current, index))
+        {
+            current = code;
+            continue;
+        }
+
+        output.write(current, width);
+        if next <= MAX_CODE
+        {
+            table.insert((current, index), next);
+            // The decoder adds each code a step behind, so the width grows once the code just added needs it.
+            if next == 1 << width && width < 12
+            {
+                width += 1;
+            }
+            next += 1;
+        }
+        else
+        {
+            output.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = 9;
+        }
+        current = index as u16;
+    }
+    output.write(current, width);
+    output.write(end, width);
+    output.finish()
+}
+
+// Sizes and counts in a GIF are 16 bits.
+fn word(value: usize, what: &str) -> io::Result<u16>
+{
+    u16::try_from(value).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("GIF {} can't be more than {}", what, u16::MAX)))
+}
+
+// An animated GIF, with every frame given its own palette of 256 colors.
+pub struct GifWriter
+{
+    file: BufWriter<File>,
+    width: usize,
+    height: usize,
+    options: VideoOptions,
+    frames: usize
+}
+
+impl GifWriter
+{
+    pub fn create(path: &str, width: usize, height: usize, options: &VideoOptions) -> io::Result<GifWriter>
+    {
+        let (w, h) = (word(width, "width")?, word(height, "height")?);
+        let repeats = word(options.loops.saturating_sub(1) as usize, "repeats")?;
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(b"GIF89a")?;
+        file.write_all(&w.to_le_bytes())?;
+        file.write_all(&h.to_le_bytes())?;
+        // No global color table, a background of index zero and square pixels.
+        file.write_all(&[0, 0, 0])?;
+
+        // The loop count counts the repeats after the first play, with zero repeating forever.
+        if options.loops != 1
+        {
+            file.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01")?;
+            file.write_all(&repeats.to_le_bytes())?;
+            file.write_all(&[0])?;
+        }
+        Ok(GifWriter { file: file, width: width, height: height, options: options.clone(), frames: 0 })
+    }
+}
+
+impl VideoWriter for GifWriter
+{
+    fn write_frame(&mut self, pixels: &[RGBA<u8>]) -> io::Result<()>
+    {
+        let mut palette = quantize::median_cut(pixels, 256);
+        let indices = quantize::map(pixels, self.width, &palette, self.options.dither);
+        palette.resize(256, [0, 0, 0]);
+
+        // Delays are in hundredths of a second, rounded so that they add up to the right length over the animation.
+        let time = |frame: usize| (frame as f64 * 100.0 / self.options.fps).round() as u64;
+        let delay = (time(self.frames + 1) - time(self.frames)).min(u16::MAX as u64) as u16;
+        self.frames += 1;
+
+        self.file.write_all(&[0x21, 0xf9, 4, 0x04])?;
+        self.file.write_all(&delay.to_le_bytes())?;
+        self.file.write_all(&[0, 0])?;
+
+        self.file.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.file.write_all(&(self.width as u16).to_le_bytes())?;
+        self.file.write_all(&(self.height as u16).to_le_bytes())?;
+        self.file.write_all(&[0x87])?;
+        for color in palette.iter()
+        {
+            self.file.write_all(color)?;
+        }
+
+        self.file.write_all(&[8])?;
+        for block in lzw(&indices).chunks(255)
+        {
+            self.file.write_all(&[block.len() as u8])?;
+            self.file.write_all(block)?;
+        }
+        self.file.write_all(&[0])
+    }
+
+    fn finish(&mut self) -> io::Result<()>
+    {
+        self.file.write_all(&[0x3b])?;
+        self.file.flush()
+    }
+}
@@ -0,0 +1,65 @@
+pub mod y4m;
+pub mod avi;
+pub mod quantize;
+pub mod gif;
+pub mod apng;
+#[cfg(test)]
+mod tests;
+
+use std::io;
+use rgb::RGBA;
+
+// Writes the frames of an animation one at a time into a single video file, which is complete once finished.
+pub trait VideoWriter
+{
+    fn write_frame(&mut self, pixels: &[RGBA<u8>]) -> io::Result<()>;
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+pub const EXTENSIONS: [&str; 4] = ["y4m", "avi", "gif", "apng"];
+
+#[derive(Clone)]
+pub struct VideoOptions
+{
+    pub fps: f64,
+    // How many times animated images play, with zero repeating forever.
+    pub loops: u32,
+    // Whether to dither colors in formats with a limited palette.
+    pub dither: bool
+}
+
+impl VideoOptions
+{
+    pub fn new(fps: f64) -> VideoOptions
+    {
+        VideoOptions { fps: fps, loops: 0, dither: false }
+    }
+}
+
+// Picks the format by the file's extension. Every frame must be width by height pixels, ordered from the top left.
+pub fn create(path: &str, width: usize, height: usize, options: &VideoOptions) -> io::Result<Box<VideoWriter>>
+{
+    match path.rsplit('.').next()
+    {
+        Some("y4m") => Ok(Box::new(y4m::Y4mWriter::create(path, width, height, options.fps)?)),
+        Some("avi") => Ok(Box::new(avi::AviWriter::create(path, width, height, options.fps)?)),
+        Some("gif") => Ok(Box::new(gif::GifWriter::create(path, width, height, options)?)),
+        Some("apng") => Ok(Box::new(apng::ApngWriter::create(path, width, height, options)?)),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown video format for {}, expected one of: {}", path, EXTENSIONS.join(", "))))
+    }
+}
+
+// Frame rates are written as fractions, and the common NTSC ones are exactly 1000/1001 of a whole number.
+pub fn frame_rate_fraction(fps: f64) -> (u32, u32)
+{
+    for &denominator in [1, 1001, 1000].iter()
+    {
+        let numerator = fps * denominator as f64;
+        let rounded = if denominator == 1001 { (numerator / 1000.0).round() * 1000.0 } else { numerator.round() };
+        if (numerator - rounded).abs() < 1e-3 * denominator as f64
+        {
+            return (rounded as u32, denominator);
+        }
+    }
+    ((fps * 1000.0).round() as u32, 1000)
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use rgb::RGBA;
+
+// Picks a palette for an image by median cut: starting from a box around all its colors, the box with the widest
+// spread along any channel is split at the median of that channel, until there are as many boxes as colors wanted.
+// Each box then gives the average of the colors in it.
+pub fn median_cut(pixels: &[RGBA<u8>], colors: usize) -> Vec<[u8; 3]>
+{
+    let spread = |colors: &[[u8; 3]]| (0..3).map(|c|
+    {
+        let (low, high) = colors.iter().fold((255, 0), |(low, high), p| (p[c].min(low), p[c].max(high)));
+        (high.saturating_sub(low), c)
+    }).max().unwrap();
+
+    // Boxes are kept with their spread, so that only the new ones need measuring after a split.
+    let all: Vec<[u8; 3]> = pixels.iter().map(|p| [p.r, p.g, p.b]).collect();
+    let mut boxes = vec![(spread(&all), all)];
+    while boxes.len() < colors
+    {
+        let index = (0..boxes.len()).max_by_key(|&i| (boxes[i].0).0).unwrap();
+        let ((range, channel), mut split) = boxes.swap_remove(index);
+        if range == 0
+        {
+            boxes.push(((range, channel), split));
+            break;
+        }
+
+        split.sort_unstable_by_key(|p| p[channel]);
+        let upper = split.split_off(split.len() / 2);
+        boxes.push((spread(&split), split));
+        boxes.push((spread(&upper), upper));
+    }
+
+    boxes.iter().map(|(_, b)| b).filter(|b| !b.is_empty()).map(|b|
+    {
+        let mut sum = [0usize; 3];
+        for p in b.iter()
+        {
+            for c in 0..3
+            {
+                sum[c] += p[c] as usize;
+            }
+        }
+        [(sum[0] / b.len()) as u8, (sum[1] / b.len()) as u8, (sum[2] / b.len()) as u8]
+    }).collect()
+}
+
+fn nearest(palette: &[[u8; 3]], color: [f64; 3]) -> usize
+{
+    let distance = |p: &[u8; 3]| (0..3).map(|c| (p[c] as f64 - color[c]) * (p[c] as f64 - color[c])).sum::<f64>();
+    (0..palette.len()).min_by(|&a, &b| distance(&palette[a]).partial_cmp(&distance(&palette[b])).unwrap()).unwrap()
+}
+
+// Gives each pixel the index of the closest palette color. With dithering, the difference from the color chosen is
+// spread over the pixels to the right and below, as Floyd-Steinberg does, so that gradients don't band.
+pub fn map(pixels: &[RGBA<u8>], width: usize, palette: &[[u8; 3]], dither: bool) -> Vec<u8>
+{
+    if !dither
+    {
+        let mut cache = HashMap::new();
+        return pixels.iter().map(|p| *cache.entry([p.r, p.g, p.b])
+            .or_insert_with(|| nearest(palette, [p.r as f64, p.g as f64, p.b as f64]) as u8)).collect();
+    }
+
+    let mut colors: Vec<[f64; 3]> = pixels.iter().map(|p| [p.r as f64, p.g as f64, p.b as f64]).collect();
+    let height = pixels.len() / width;
+    let mut indices = Vec::with_capacity(pixels.len());
+    for y in 0..height
+    {
+        for x in 0..width
+        {
+            let color = colors[y * width + x];
+            let index = nearest(palette, color);
+            indices.push(index as u8);
+
+            let error: Vec<f64> = (0..3).map(|c| color[c] - palette[index][c] as f64).collect();
+            let mut spread = |dx: isize, dy: usize, weight: f64|
+            {
+                let nx = x as isize + dx;
+                if nx >= 0 && (nx as usize) < width && y + dy < height
+                {
+                    let neighbor = &mut colors[(y + dy) * width + nx as usize];
+                    for c in 0..3
+                    {
+                        neighbor[c] = (neighbor[c] + error[c] * weight).clamp(0.0, 255.0);
+                    }
+                }
+            };
+            spread(1, 0, 7.0 / 16.0);
+            spread(-1, 1, 3.0 / 16.0);
+            spread(0, 1, 5.0 / 16.0);
+            spread(1, 1, 1.0 / 16.0);
+        }
+    }
+    indices
+}
@@ -0,0 +1,340 @@
+use std::env;
+use std::fs;
+use rgb::RGBA;
+use lodepng;
+use video::{self, VideoOptions, frame_rate_fraction};
+use video::{apng, gif, quantize};
+use video::y4m::to_ycbcr;
+
+fn pixel(r: u8, g: u8, b: u8) -> RGBA<u8>
+{
+    RGBA { r: r, g: g, b: b, a: 255 }
+}
+
+fn write(name: &str, frames: &[Vec<RGBA<u8>>], width: usize, height: usize) -> Vec<u8>
+{
+    let path = env::temp_dir().join(name);
+    let path = path.to_str().unwrap();
+    let mut writer = video::create(path, width, height, &VideoOptions::new(24.0)).unwrap();
+    for frame in frames
+    {
+        writer.write_frame(frame).unwrap();
+    }
+    writer.finish().unwrap();
+    let bytes = fs::read(path).unwrap();
+    fs::remove_file(path).unwrap();
+    bytes
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32
+{
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+#[test]
+fn frame_rates_are_exact_fractions()
+{
+    assert_eq!(frame_rate_fraction(24.0), (24, 1));
+    assert_eq!(frame_rate_fraction(30000.0 / 1001.0), (30000, 1001));
+    assert_eq!(frame_rate_fraction(23.976), (24000, 1001));
+    assert_eq!(frame_rate_fraction(12.5), (12500, 1000));
+}
+
+#[test]
+fn ycbcr_uses_studio_range_bt601()
+{
+    let round = |(y, cb, cr): (f64, f64, f64)| (y.round() as u8, cb.round() as u8, cr.round() as u8);
+    assert_eq!(round(to_ycbcr(pixel(0, 0, 0))), (16, 128, 128));
+    assert_eq!(round(to_ycbcr(pixel(255, 255, 255))), (235, 128, 128));
+    assert_eq!(round(to_ycbcr(pixel(255, 0, 0))), (81, 90, 240));
+    assert_eq!(round(to_ycbcr(pixel(0, 0, 255))), (41, 240, 110));
+}
+
+#[test]
+fn y4m_frames_have_subsampled_chroma()
+{
+    // A 3x2 frame has 2x1 chroma samples, the second of them covering only the last column.
+    let frame = vec![pixel(255, 255, 255), pixel(255, 255, 255), pixel(255, 0, 0), pixel(0, 0, 0), pixel(0, 0, 0), pixel(255, 0, 0)];
+    let bytes = write("raytracer_test.y4m", &[frame.clone(), frame], 3, 2);
+    let header = b"YUV4MPEG2 W3 H2 F24:1 Ip A1:1 C420jpeg XYSCSS=420JPEG\n";
+    assert_eq!(&bytes[..header.len()], &header[..]);
+
+    let frame = [&b"FRAME\n"[..], &[235, 235, 81, 16, 16, 81], &[128, 90], &[128, 240]].concat();
+    assert_eq!(&bytes[header.len()..], &[&frame[..], &frame[..]].concat()[..]);
+}
+
+#[test]
+fn avi_sizes_and_index_are_filled_in()
+{
+    let frames = vec![vec![pixel(10, 20, 30), pixel(40, 50, 60), pixel(70, 80, 90), pixel(100, 110, 120)]; 3];
+    let bytes = write("raytracer_test.avi", &frames, 2, 2);
+    assert!(&bytes[..4] == b"RIFF" && &bytes[8..12] == b"AVI ");
+    assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8);
+    // The frame count, in both the main and stream headers, and the frame rate as 1/24.
+    assert_eq!((u32_at(&bytes, 48), u32_at(&bytes, 140)), (3, 3));
+    assert_eq!((u32_at(&bytes, 128), u32_at(&bytes, 132)), (1, 24));
+
+    // Rows of two pixels are padded to eight bytes, and the bottom row comes first.
+    let frame_size = 16;
+    assert_eq!(&bytes[220..224], b"movi");
+    assert_eq!(u32_at(&bytes, 216) as usize, 4 + 3 * (8 + frame_size));
+    assert_eq!(&bytes[224..228], b"00db");
+    assert_eq!(&bytes[232..240], &[90, 80, 70, 120, 110, 100, 0, 0]);
+    assert_eq!(&bytes[240..246], &[30, 20, 10, 60, 50, 40]);
+
+    let index = 224 + 3 * (8 + frame_size);
+    assert_eq!(&bytes[index..index + 4], b"idx1");
+    assert_eq!(u32_at(&bytes, index + 4), 3 * 16);
+    for i in 0..3
+    {
+        let entry = index + 8 + i * 16;
+        assert_eq!(&bytes[entry..entry + 4], b"00db");
+        assert_eq!(u32_at(&bytes, entry + 8) as usize, 4 + i * (8 + frame_size));
+        assert_eq!(u32_at(&bytes, entry + 12) as usize, frame_size);
+    }
+}
+
+#[test]
+fn avi_refuses_frames_too_large_for_its_sizes()
+{
+    // A 6 GiB frame can't be described by a 32-bit size, so the writer turns it down before writing anything.
+    let path = env::temp_dir().join("raytracer_test_large.avi");
+    assert!(video::create(path.to_str().unwrap(), 65536, 32768, &VideoOptions::new(24.0)).is_err());
+    assert!(!path.exists());
+}
+
+// Undoes GIF's LZW, reading codes as the decoders in browsers do.
+fn unlzw(bytes: &[u8]) -> Vec<u8>
+{
+    let (clear, end) = (256usize, 257usize);
+    let mut table: Vec<Vec<u8>> = Vec::new();
+    let (mut width, mut position) = (9, 0);
+    let mut previous: Option<usize> = None;
+    let mut output = Vec::new();
+    loop
+    {
+        let mut code = 0;
+        for bit in 0..width
+        {
+            code |= ((bytes[(position + bit) / 8] >> ((position + bit) % 8)) as usize & 1) << bit;
+        }
+        position += width;
+
+        if code == clear
+        {
+            table = (0..256).map(|i| vec![i as u8]).chain(vec![vec![], vec![]]).collect();
+            width = 9;
+            previous = None;
+            continue;
+        }
+        if code == end
+        {
+            return output;
+        }
+
+        let entry = match previous
+        {
+            None => table[code].clone(),
+            Some(p) =>
+            {
+                let entry = if code < table.len() { table[code].clone() } else { [&table[p][..], &table[p][..1]].concat() };
+                let added = [&table[p][..], &entry[..1]].concat();
+                table.push(added);
+                if table.len() == 1 << width && width < 12
+                {
+                    width += 1;
+                }
+                entry
+            }
+        };
+        output.extend_from_slice(&entry);
+        previous = Some(code);
+    }
+}
+
+#[test]
+fn lzw_round_trips_past_the_largest_code()
+{
+    // Enough varied data to fill the table and clear it a few times.
+    let mut state = 1u32;
+    let indices: Vec<u8> = (0..100000).map(|i|
+    {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        if i % 7 < 3 { (state >> 24) as u8 } else { (i / 50) as u8 }
+    }).collect();
+    assert!(unlzw(&gif::lzw(&indices)) == indices);
+    assert!(unlzw(&gif::lzw(&[3])) == vec![3]);
+}
+
+#[test]
+fn median_cut_keeps_images_with_few_colors_exact()
+{
+    let colors = [pixel(255, 0, 0), pixel(0, 255, 0), pixel(0, 0, 255), pixel(20, 20, 20)];
+    let pixels: Vec<RGBA<u8>> = (0..64).map(|i| colors[i * 7 % 4]).collect();
+    let palette = quantize::median_cut(&pixels, 256);
+    assert_eq!(palette.len(), 4);
+    for &dither in [false, true].iter()
+    {
+        let indices = quantize::map(&pixels, 8, &palette, dither);
+        for (p, &i) in pixels.iter().zip(indices.iter())
+        {
+            assert_eq!(palette[i as usize], [p.r, p.g, p.b]);
+        }
+    }
+
+    // A gradient with more colors than the palette is matched closely, and dithering keeps its average.
+    let gradient: Vec<RGBA<u8>> = (0..256 * 16).map(|i| pixel((i % 256) as u8, (i % 256) as u8, (i / 256) as u8)).collect();
+    let palette = quantize::median_cut(&gradient, 16);
+    assert_eq!(palette.len(), 16);
+    let plain = quantize::map(&gradient, 256, &palette, false);
+    assert!(gradient.iter().zip(plain.iter()).all(|(p, &i)| (palette[i as usize][0] as i32 - p.r as i32).abs() <= 16));
+    let dithered = quantize::map(&gradient, 256, &palette, true);
+    let average = |indices: &[u8]| indices.iter().map(|&i| palette[i as usize][0] as f64).sum::<f64>() / indices.len() as f64;
+    assert!((average(&dithered) - 127.5).abs() < 1.0);
+}
+
+#[test]
+fn gif_frames_decode_to_the_images()
+{
+    let frame = |shift: u8| (0..12).map(|i| pixel(i * 20 + shift, 255 - i * 20, shift)).collect::<Vec<_>>();
+    let path = env::temp_dir().join("raytracer_test.gif");
+    let path = path.to_str().unwrap();
+    let mut options = VideoOptions::new(30.0);
+    options.loops = 3;
+    let mut writer = video::create(path, 4, 3, &options).unwrap();
+    for shift in 0..3
+    {
+        writer.write_frame(&frame(shift)).unwrap();
+    }
+    writer.finish().unwrap();
+    let bytes = fs::read(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(&bytes[..13], &[b'G', b'I', b'F', b'8', b'9', b'a', 4, 0, 3, 0, 0, 0, 0]);
+    // Three plays are the first and two repeats.
+    assert_eq!(&bytes[13..32], b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x02\x00\x00");
+    let mut offset = 32;
+    let mut delays = Vec::new();
+    for shift in 0..3
+    {
+        assert_eq!(&bytes[offset..offset + 4], &[0x21, 0xf9, 4, 0x04]);
+        delays.push(u16::from_le_bytes([bytes[offset + 4], bytes[offset + 5]]));
+        offset += 8;
+        assert_eq!(&bytes[offset..offset + 10], &[0x2c, 0, 0, 0, 0, 4, 0, 3, 0, 0x87]);
+        let palette = &bytes[offset + 10..offset + 10 + 768];
+        offset += 10 + 768;
+        assert_eq!(bytes[offset], 8);
+        offset += 1;
+        let mut data = Vec::new();
+        while bytes[offset] != 0
+        {
+            data.extend_from_slice(&bytes[offset + 1..offset + 1 + bytes[offset] as usize]);
+            offset += 1 + bytes[offset] as usize;
+        }
+        offset += 1;
+
+        let decoded: Vec<RGBA<u8>> = unlzw(&data).iter().map(|&i| pixel(palette[3 * i as usize], palette[3 * i as usize + 1], palette[3 * i as usize + 2])).collect();
+        assert!(decoded == frame(shift));
+    }
+    // A thirtieth of a second is between three and four hundredths, which alternate to keep time.
+    assert_eq!(delays, vec![3, 4, 3]);
+    assert_eq!(&bytes[offset..], &[0x3b]);
+}
+
+#[test]
+fn gif_delays_keep_time_past_the_sixteen_bit_timestamps()
+{
+    // At ten seconds a frame, the 66th frame starts past the 655 seconds hundredths of a second fit in 16 bits.
+    let path = env::temp_dir().join("raytracer_test_long.gif");
+    let path = path.to_str().unwrap();
+    let mut writer = video::create(path, 1, 1, &VideoOptions::new(0.1)).unwrap();
+    for _ in 0..70
+    {
+        writer.write_frame(&[pixel(1, 2, 3)]).unwrap();
+    }
+    writer.finish().unwrap();
+    let bytes = fs::read(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    let mut offset = 32;
+    let mut delays = Vec::new();
+    while bytes[offset] == 0x21
+    {
+        delays.push(u16::from_le_bytes([bytes[offset + 4], bytes[offset + 5]]));
+        offset += 8 + 10 + 768 + 1;
+        while bytes[offset] != 0
+        {
+            offset += 1 + bytes[offset] as usize;
+        }
+        offset += 1;
+    }
+    assert_eq!(delays, vec![1000; 70]);
+}
+
+#[test]
+fn gif_refuses_sizes_past_sixteen_bits()
+{
+    let path = env::temp_dir().join("raytracer_test_wide.gif");
+    assert!(video::create(path.to_str().unwrap(), 70000, 1, &VideoOptions::new(24.0)).is_err());
+    assert!(!path.exists());
+    let mut options = VideoOptions::new(24.0);
+    options.loops = 70000;
+    assert!(video::create(path.to_str().unwrap(), 1, 1, &options).is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn apng_delays_fit_in_sixteen_bits()
+{
+    assert_eq!(apng::frame_delay(25.0), (1, 25));
+    assert_eq!(apng::frame_delay(30000.0 / 1001.0), (1001, 30000));
+    // 100500/1000 only fits once it's reduced, and 65537/1000 doesn't even then.
+    assert_eq!(apng::frame_delay(100.5), (2, 201));
+    assert_eq!(apng::frame_delay(65.537), (15, 1000));
+    assert_eq!(apng::frame_delay(70000.5), (1, 1000));
+}
+
+#[test]
+fn apng_frames_follow_the_first_image()
+{
+    assert_eq!(apng::crc32(b"IEND"), 0xae426082);
+    let frames: Vec<Vec<RGBA<u8>>> = (0..3).map(|f| (0..6).map(|i| pixel(i * 40, f * 100, 7)).collect()).collect();
+    let mut options = VideoOptions::new(25.0);
+    options.loops = 2;
+    let path = env::temp_dir().join("raytracer_test.apng");
+    let path = path.to_str().unwrap();
+    let mut writer = video::create(path, 3, 2, &options).unwrap();
+    for frame in frames.iter()
+    {
+        writer.write_frame(frame).unwrap();
+    }
+    writer.finish().unwrap();
+    let bytes = fs::read(path).unwrap();
+
+    // Without animation support, it's the first frame.
+    let image = lodepng::decode32_file(path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert!((image.width, image.height) == (3, 2) && image.buffer == frames[0]);
+
+    let mut kinds = Vec::new();
+    let mut offset = 8;
+    while offset < bytes.len()
+    {
+        let length = u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let kind = String::from_utf8(bytes[offset + 4..offset + 8].to_vec()).unwrap();
+        let data = &bytes[offset + 8..offset + 8 + length];
+        let crc = u32::from_be_bytes([bytes[offset + 8 + length], bytes[offset + 9 + length], bytes[offset + 10 + length], bytes[offset + 11 + length]]);
+        assert_eq!(crc, apng::crc32(&bytes[offset + 4..offset + 8 + length]), "{} has the wrong CRC", kind);
+        match kind.as_str()
+        {
+            "acTL" => assert_eq!(data, &[0, 0, 0, 3, 0, 0, 0, 2]),
+            // A 25th of a second, with the sequence number counting every frame chunk.
+            "fcTL" => assert_eq!(&data[20..24], &[0, 1, 0, 25]),
+            "fdAT" => assert!(u32::from_be_bytes([data[0], data[1], data[2], data[3]]) > 0),
+            _ => ()
+        }
+        kinds.push(kind);
+        offset += length + 12;
+    }
+    assert_eq!(kinds, vec!["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "fcTL", "fdAT", "IEND"]);
+}
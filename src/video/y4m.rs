@@ -0,0 +1,76 @@
+use std::fs::File;
+use std::io::{self, Write, BufWriter};
+use rgb::RGBA;
+use video::{VideoWriter, frame_rate_fraction};
+
+// Converts gamma encoded RGB to studio range Y'CbCr with the BT.601 coefficients, which is what players assume for
+// streams that don't say otherwise.
+pub fn to_ycbcr(pixel: RGBA<u8>) -> (f64, f64, f64)
+{
+    let (r, g, b) = (pixel.r as f64 / 255.0, pixel.g as f64 / 255.0, pixel.b as f64 / 255.0);
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    (16.0 + 219.0 * y, 128.0 + 224.0 * (b - y) / 1.772, 128.0 + 224.0 * (r - y) / 1.402)
+}
+
+// A YUV4MPEG2 stream of 4:2:0 frames, with each chroma sample the average of the 2x2 pixels it's centered on.
+pub struct Y4mWriter
+{
+    file: BufWriter<File>,
+    width: usize,
+    height: usize
+}
+
+impl Y4mWriter
+{
+    pub fn create(path: &str, width: usize, height: usize, fps: f64) -> io::Result<Y4mWriter>
+    {
+        let mut file = BufWriter::new(File::create(path)?);
+        let (numerator, denominator) = frame_rate_fraction(fps);
+        writeln!(file, "YUV4MPEG2 W{} H{} F{}:{} Ip A1:1 C420jpeg XYSCSS=420JPEG", width, height, numerator, denominator)?;
+        Ok(Y4mWriter { file: file, width: width, height: height })
+    }
+}
+
+impl VideoWriter for Y4mWriter
+{
+    fn write_frame(&mut self, pixels: &[RGBA<u8>]) -> io::Result<()>
+    {
+        let ycbcr: Vec<(f64, f64, f64)> = pixels.iter().map(|p| to_ycbcr(*p)).collect();
+        let byte = |v: f64| v.round().clamp(0.0, 255.0) as u8;
+
+        self.file.write_all(b"FRAME\n")?;
+        let luma: Vec<u8> = ycbcr.iter().map(|c| byte(c.0)).collect();
+        self.file.write_all(&luma)?;
+
+        // Odd sizes leave a last row or column of chroma covering only the pixels there are.
+        let (chroma_width, chroma_height) = (self.width.div_ceil(2), self.height.div_ceil(2));
+        let mut cb = Vec::with_capacity(chroma_width * chroma_height);
+        let mut cr = Vec::with_capacity(chroma_width * chroma_height);
+        for cy in 0..chroma_height
+        {
+            for cx in 0..chroma_width
+            {
+                let (mut sum_cb, mut sum_cr, mut count) = (0.0, 0.0, 0.0);
+                for y in 2 * cy..(2 * cy + 2).min(self.height)
+                {
+                    for x in 2 * cx..(2 * cx + 2).min(self.width)
+                    {
+                        let c = ycbcr[y * self.width + x];
+                        sum_cb += c.1;
+                        sum_cr += c.2;
+                        count += 1.0;
+                    }
+                }
+                cb.push(byte(sum_cb / count));
+                cr.push(byte(sum_cr / count));
+            }
+        }
+        self.file.write_all(&cb)?;
+        self.file.write_all(&cr)
+    }
+
+    fn finish(&mut self) -> io::Result<()>
+    {
+        self.file.flush()
+    }
+}